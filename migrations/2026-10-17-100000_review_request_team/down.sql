@@ -0,0 +1,12 @@
+-- Collapse review requests for members of several teams back into one.
+DELETE FROM fcp_review_request a
+    USING fcp_review_request b
+    WHERE a.fk_proposal = b.fk_proposal
+      AND a.fk_reviewer = b.fk_reviewer
+      AND a.id > b.id;
+
+ALTER TABLE fcp_review_request DROP CONSTRAINT fcp_review_request_fk_proposal_fk_reviewer_team_label_key;
+ALTER TABLE fcp_review_request ADD CONSTRAINT fcp_review_request_fk_proposal_fk_reviewer_key
+    UNIQUE (fk_proposal, fk_reviewer);
+
+ALTER TABLE fcp_review_request DROP COLUMN team_label;
@@ -0,0 +1,8 @@
+-- Record which team each review was requested on behalf of, so that every team
+-- of a multi-team FCP has to reach its own quorum. A member of several teams
+-- gets one review request per team.
+ALTER TABLE fcp_review_request ADD COLUMN team_label VARCHAR;
+
+ALTER TABLE fcp_review_request DROP CONSTRAINT fcp_review_request_fk_proposal_fk_reviewer_key;
+ALTER TABLE fcp_review_request ADD CONSTRAINT fcp_review_request_fk_proposal_fk_reviewer_team_label_key
+    UNIQUE (fk_proposal, fk_reviewer, team_label);
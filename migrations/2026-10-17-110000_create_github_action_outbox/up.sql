@@ -0,0 +1,17 @@
+-- Outbox of GitHub side effects (comments, labels, closing issues) which are
+-- recorded in the same transaction as the FCP state change causing them, and
+-- performed later by a worker which retries them until they succeed.
+CREATE TABLE github_action (
+    id SERIAL PRIMARY KEY,
+    fk_issue INTEGER NOT NULL REFERENCES issue (id),
+    action VARCHAR NOT NULL,
+    body VARCHAR,
+    label VARCHAR,
+    fk_comment BIGINT,
+    created_at TIMESTAMP NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error VARCHAR,
+    completed_at TIMESTAMP
+);
+
+CREATE INDEX github_action_pending_idx ON github_action (id) WHERE completed_at IS NULL;
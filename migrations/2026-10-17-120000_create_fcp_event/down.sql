@@ -0,0 +1 @@
+DROP TABLE fcp_event;
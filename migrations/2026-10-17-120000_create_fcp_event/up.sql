@@ -0,0 +1,15 @@
+-- Append-only log of what happened to each FCP proposal. Proposals are deleted
+-- when they're cancelled, so fk_proposal deliberately isn't a foreign key: the
+-- events have to outlive the proposal they describe.
+CREATE TABLE fcp_event (
+    id SERIAL PRIMARY KEY,
+    fk_proposal INTEGER NOT NULL,
+    fk_issue INTEGER NOT NULL REFERENCES issue (id),
+    event VARCHAR NOT NULL,
+    detail VARCHAR,
+    fk_actor INTEGER REFERENCES githubuser (id),
+    fk_comment BIGINT,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX fcp_event_proposal_idx ON fcp_event (fk_proposal, id);
@@ -0,0 +1,9 @@
+-- NOT VALID: comments may have been deleted in the meantime
+ALTER TABLE fcp_proposal ADD CONSTRAINT fcp_proposal_fk_initiating_comment_fkey
+    FOREIGN KEY (fk_initiating_comment) REFERENCES issuecomment (id) NOT VALID;
+ALTER TABLE poll ADD CONSTRAINT poll_fk_initiating_comment_fkey
+    FOREIGN KEY (fk_initiating_comment) REFERENCES issuecomment (id) NOT VALID;
+ALTER TABLE fcp_concern ADD CONSTRAINT fcp_concern_fk_resolved_comment_fkey
+    FOREIGN KEY (fk_resolved_comment) REFERENCES issuecomment (id) NOT VALID;
+ALTER TABLE rfc_feedback_request ADD CONSTRAINT rfc_feedback_request_fk_feedback_comment_fkey
+    FOREIGN KEY (fk_feedback_comment) REFERENCES issuecomment (id) NOT VALID;
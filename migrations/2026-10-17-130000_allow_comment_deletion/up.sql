@@ -0,0 +1,7 @@
+-- Comments can be deleted on GitHub. These columns only record which comment
+-- did something (proposed an FCP, resolved a concern, ...), so they keep the
+-- id of a deleted comment rather than stopping it from being deleted.
+ALTER TABLE fcp_proposal DROP CONSTRAINT fcp_proposal_fk_initiating_comment_fkey;
+ALTER TABLE poll DROP CONSTRAINT poll_fk_initiating_comment_fkey;
+ALTER TABLE fcp_concern DROP CONSTRAINT fcp_concern_fk_resolved_comment_fkey;
+ALTER TABLE rfc_feedback_request DROP CONSTRAINT rfc_feedback_request_fk_feedback_comment_fkey;
@@ -0,0 +1,2 @@
+ALTER TABLE fcp_review_request DROP COLUMN abstain_reason;
+ALTER TABLE fcp_review_request DROP COLUMN abstained;
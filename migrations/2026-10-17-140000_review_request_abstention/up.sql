@@ -0,0 +1,4 @@
+-- Let reviewers abstain from reviewing a proposal, optionally saying why.
+-- Abstentions don't count towards the quorum either way.
+ALTER TABLE fcp_review_request ADD COLUMN abstained BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE fcp_review_request ADD COLUMN abstain_reason VARCHAR;
@@ -0,0 +1,4 @@
+-- Let reviewers hand their review requests to another member of the same
+-- team. fk_reviewer is whoever has to review now, fk_delegated_from who the
+-- review was originally requested from.
+ALTER TABLE fcp_review_request ADD COLUMN fk_delegated_from INTEGER REFERENCES githubuser (id);
@@ -0,0 +1,4 @@
+-- Let team members extend or shorten a running final comment period. When
+-- set, extended_end replaces the end worked out from fcp_start and the
+-- configured length of an FCP.
+ALTER TABLE fcp_proposal ADD COLUMN extended_end TIMESTAMP;
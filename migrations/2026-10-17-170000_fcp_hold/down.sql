@@ -0,0 +1,3 @@
+ALTER TABLE fcp_proposal DROP COLUMN fk_held_by;
+ALTER TABLE fcp_proposal DROP COLUMN hold_reason;
+ALTER TABLE fcp_proposal DROP COLUMN held_at;
@@ -0,0 +1,6 @@
+-- Let team members put a proposal on hold without raising a concern. While
+-- held_at is set the FCP can't start, or its clock is stopped if it already
+-- has; taking it off hold moves the end back by however long it was held.
+ALTER TABLE fcp_proposal ADD COLUMN held_at TIMESTAMP;
+ALTER TABLE fcp_proposal ADD COLUMN hold_reason VARCHAR;
+ALTER TABLE fcp_proposal ADD COLUMN fk_held_by INTEGER REFERENCES githubuser (id);
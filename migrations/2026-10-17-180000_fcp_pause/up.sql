@@ -0,0 +1,3 @@
+-- Repositories can have an FCP which a concern pauses resume where it left
+-- off, rather than start over. This is how much of it was left, in seconds.
+ALTER TABLE fcp_proposal ADD COLUMN fcp_remaining_secs BIGINT;
@@ -25,6 +25,11 @@
 //!   not defined, logging will default to `info!()` and above.
 //! * `GITHUB_SCRAPE_INTERVAL`: time (in minutes) to wait in between GitHub scrapes (scraping is
 //!   disabled if this environment variable is omitted)
+//! * `LABEL_RECONCILE_INTERVAL`: time (in minutes) to wait in between checking that the labels of
+//!   issues with FCPs match the FCP state (the check is disabled if this environment variable is
+//!   omitted)
+//! * `LABEL_RECONCILE_DRY_RUN`: if `true`, only report labels which need fixing instead of fixing
+//!   them. Defaults to `false`.
 //! * `POST_COMMENTS`: whether to post RFC bot comments on issues -- either `true` or `false`. Be
 //!   very careful setting to true when testing -- it will post comments using whatever account is
 //!   associated with the GitHub API key you provide.
@@ -59,6 +64,8 @@ pub struct Config {
     pub github_user_agent: String,
     pub github_webhook_secrets: Vec<String>,
     pub github_interval_mins: Option<u64>,
+    pub label_reconcile_interval_mins: Option<u64>,
+    pub label_reconcile_dry_run: bool,
     pub post_comments: bool,
 }
 
@@ -76,6 +83,8 @@ const GITHUB_TOKEN: &str = "GITHUB_ACCESS_TOKEN";
 const GITHUB_WEBHOOK_SECRETS: &str = "GITHUB_WEBHOOK_SECRETS";
 const GITHUB_UA: &str = "GITHUB_USER_AGENT";
 const GITHUB_INTERVAL: &str = "GITHUB_SCRAPE_INTERVAL";
+const LABEL_RECONCILE_INTERVAL: &str = "LABEL_RECONCILE_INTERVAL";
+const LABEL_RECONCILE_DRY_RUN: &str = "LABEL_RECONCILE_DRY_RUN";
 const POST_COMMENTS: &str = "POST_COMMENTS";
 
 // this is complex, but we'll shortly need a lot more config items
@@ -115,6 +124,21 @@ pub fn init() -> Result<Config, Vec<&'static str>> {
             None
         };
 
+        let label_interval = if let Ok(val) = env::var(LABEL_RECONCILE_INTERVAL) {
+            Some(ok_or!(
+                val.parse::<u64>(),
+                throw!(vec![LABEL_RECONCILE_INTERVAL])
+            ))
+        } else {
+            None
+        };
+
+        let label_dry_run = if let Ok(val) = env::var(LABEL_RECONCILE_DRY_RUN) {
+            ok_or!(val.parse::<bool>(), throw!(vec![LABEL_RECONCILE_DRY_RUN]))
+        } else {
+            false
+        };
+
         let post_comments = vars.remove(POST_COMMENTS).unwrap().parse::<bool>();
         let post_comments = ok_or!(post_comments, throw!(vec![POST_COMMENTS]));
 
@@ -128,6 +152,8 @@ pub fn init() -> Result<Config, Vec<&'static str>> {
             github_user_agent: gh_ua,
             github_webhook_secrets: webhook_secrets,
             github_interval_mins: gh_interval,
+            label_reconcile_interval_mins: label_interval,
+            label_reconcile_dry_run: label_dry_run,
             post_comments,
         })
     } else {
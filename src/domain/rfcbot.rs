@@ -34,6 +34,8 @@ pub struct Poll {
 #[derive(Clone, Debug, Eq, Ord, Insertable, PartialEq, PartialOrd)]
 #[table_name = "fcp_proposal"]
 pub struct NewFcpProposal<'a> {
+    /// Left to the database, unless a replayed proposal keeps the id it had.
+    pub id: Option<i32>,
     pub fk_issue: i32,
     pub fk_initiator: i32,
     pub fk_initiating_comment: i64,
@@ -76,14 +78,23 @@ pub struct FcpProposal {
     pub fk_bot_tracking_comment: i64,
     pub fcp_start: Option<NaiveDateTime>,
     pub fcp_closed: bool,
+    /// When the FCP ends, once it has started. Extending, shortening or holding the FCP moves it.
+    pub extended_end: Option<NaiveDateTime>,
+    /// When the proposal was put on hold, if it is on hold.
+    pub held_at: Option<NaiveDateTime>,
+    pub hold_reason: Option<String>,
+    pub fk_held_by: Option<i32>,
+    /// How long the FCP had left when a concern paused it, if it is to resume from there.
+    pub fcp_remaining_secs: Option<i64>,
 }
 
 #[derive(Clone, Debug, Eq, Insertable, Ord, PartialEq, PartialOrd, Serialize)]
 #[table_name = "fcp_review_request"]
-pub struct NewFcpReviewRequest {
+pub struct NewFcpReviewRequest<'a> {
     pub fk_proposal: i32,
     pub fk_reviewer: i32,
     pub reviewed: bool,
+    pub team_label: Option<&'a str>,
 }
 
 #[derive(
@@ -95,6 +106,10 @@ pub struct FcpReviewRequest {
     pub fk_proposal: i32,
     pub fk_reviewer: i32,
     pub reviewed: bool,
+    pub team_label: Option<String>,
+    pub abstained: bool,
+    pub abstain_reason: Option<String>,
+    pub fk_delegated_from: Option<i32>,
 }
 
 #[derive(Clone, Debug, Eq, Insertable, Ord, PartialEq, PartialOrd)]
@@ -136,3 +151,54 @@ pub struct FeedbackRequest {
     pub fk_issue: i32,
     pub fk_feedback_comment: Option<i64>,
 }
+
+#[derive(Clone, Debug, Eq, Insertable, Ord, PartialEq, PartialOrd)]
+#[table_name = "github_action"]
+pub struct NewGitHubAction<'a> {
+    pub fk_issue: i32,
+    pub action: &'a str,
+    pub body: Option<&'a str>,
+    pub label: Option<&'a str>,
+    pub fk_comment: Option<i64>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(AsChangeset, Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Queryable)]
+#[table_name = "github_action"]
+#[changeset_options(treat_none_as_null = "true")]
+pub struct GitHubAction {
+    pub id: i32,
+    pub fk_issue: i32,
+    pub action: String,
+    pub body: Option<String>,
+    pub label: Option<String>,
+    pub fk_comment: Option<i64>,
+    pub created_at: NaiveDateTime,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Clone, Debug, Eq, Insertable, Ord, PartialEq, PartialOrd)]
+#[table_name = "fcp_event"]
+pub struct NewFcpEvent<'a> {
+    pub fk_proposal: i32,
+    pub fk_issue: i32,
+    pub event: &'a str,
+    pub detail: Option<&'a str>,
+    pub fk_actor: Option<i32>,
+    pub fk_comment: Option<i64>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Queryable, Serialize)]
+pub struct FcpEvent {
+    pub id: i32,
+    pub fk_proposal: i32,
+    pub fk_issue: i32,
+    pub event: String,
+    pub detail: Option<String>,
+    pub fk_actor: Option<i32>,
+    pub fk_comment: Option<i64>,
+    pub created_at: NaiveDateTime,
+}
@@ -42,6 +42,62 @@ table! {
     }
 }
 
+table! {
+    /// Representation of the `fcp_event` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    fcp_event (id) {
+        /// The `id` column of the `fcp_event` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `fk_proposal` column of the `fcp_event` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fk_proposal -> Int4,
+        /// The `fk_issue` column of the `fcp_event` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fk_issue -> Int4,
+        /// The `event` column of the `fcp_event` table.
+        ///
+        /// Its SQL type is `Varchar`.
+        ///
+        /// (Automatically generated by Diesel.)
+        event -> Varchar,
+        /// The `detail` column of the `fcp_event` table.
+        ///
+        /// Its SQL type is `Nullable<Varchar>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        detail -> Nullable<Varchar>,
+        /// The `fk_actor` column of the `fcp_event` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fk_actor -> Nullable<Int4>,
+        /// The `fk_comment` column of the `fcp_event` table.
+        ///
+        /// Its SQL type is `Nullable<Int8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fk_comment -> Nullable<Int8>,
+        /// The `created_at` column of the `fcp_event` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+    }
+}
+
 table! {
     /// Representation of the `fcp_proposal` table.
     ///
@@ -95,6 +151,36 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         fcp_closed -> Bool,
+        /// The `extended_end` column of the `fcp_proposal` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamp>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        extended_end -> Nullable<Timestamp>,
+        /// The `held_at` column of the `fcp_proposal` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamp>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        held_at -> Nullable<Timestamp>,
+        /// The `hold_reason` column of the `fcp_proposal` table.
+        ///
+        /// Its SQL type is `Nullable<Varchar>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        hold_reason -> Nullable<Varchar>,
+        /// The `fk_held_by` column of the `fcp_proposal` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fk_held_by -> Nullable<Int4>,
+        /// The `fcp_remaining_secs` column of the `fcp_proposal` table.
+        ///
+        /// Its SQL type is `Nullable<Int8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fcp_remaining_secs -> Nullable<Int8>,
     }
 }
 
@@ -127,6 +213,98 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         reviewed -> Bool,
+        /// The `team_label` column of the `fcp_review_request` table.
+        ///
+        /// Its SQL type is `Nullable<Varchar>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        team_label -> Nullable<Varchar>,
+        /// The `abstained` column of the `fcp_review_request` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        abstained -> Bool,
+        /// The `abstain_reason` column of the `fcp_review_request` table.
+        ///
+        /// Its SQL type is `Nullable<Varchar>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        abstain_reason -> Nullable<Varchar>,
+        /// The `fk_delegated_from` column of the `fcp_review_request` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fk_delegated_from -> Nullable<Int4>,
+    }
+}
+
+table! {
+    /// Representation of the `github_action` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    github_action (id) {
+        /// The `id` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `fk_issue` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fk_issue -> Int4,
+        /// The `action` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Varchar`.
+        ///
+        /// (Automatically generated by Diesel.)
+        action -> Varchar,
+        /// The `body` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Nullable<Varchar>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        body -> Nullable<Varchar>,
+        /// The `label` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Nullable<Varchar>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        label -> Nullable<Varchar>,
+        /// The `fk_comment` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Nullable<Int8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        fk_comment -> Nullable<Int8>,
+        /// The `created_at` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Timestamp`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamp,
+        /// The `attempts` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        attempts -> Int4,
+        /// The `last_error` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Nullable<Varchar>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        last_error -> Nullable<Varchar>,
+        /// The `completed_at` column of the `github_action` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamp>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        completed_at -> Nullable<Timestamp>,
     }
 }
 
@@ -718,10 +896,13 @@ table! {
 
 joinable!(fcp_concern -> fcp_proposal (fk_proposal));
 joinable!(fcp_concern -> githubuser (fk_initiator));
+joinable!(fcp_event -> githubuser (fk_actor));
+joinable!(fcp_event -> issue (fk_issue));
 joinable!(fcp_proposal -> githubuser (fk_initiator));
 joinable!(fcp_proposal -> issue (fk_issue));
 joinable!(fcp_review_request -> fcp_proposal (fk_proposal));
 joinable!(fcp_review_request -> githubuser (fk_reviewer));
+joinable!(github_action -> issue (fk_issue));
 joinable!(issue -> milestone (fk_milestone));
 joinable!(issuecomment -> githubuser (fk_user));
 joinable!(issuecomment -> issue (fk_issue));
@@ -735,12 +916,13 @@ joinable!(poll_response_request -> poll (fk_poll));
 joinable!(pullrequest -> githubuser (fk_assignee));
 joinable!(pullrequest -> milestone (fk_milestone));
 joinable!(rfc_feedback_request -> issue (fk_issue));
-joinable!(rfc_feedback_request -> issuecomment (fk_feedback_comment));
 
 allow_tables_to_appear_in_same_query!(
     fcp_concern,
+    fcp_event,
     fcp_proposal,
     fcp_review_request,
+    github_action,
     githubsync,
     githubuser,
     issue,
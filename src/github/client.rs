@@ -183,6 +183,11 @@ impl Client {
         );
         let res = self.delete(&url)?;
 
+        // the label isn't on the issue, which is what we wanted anyway
+        if StatusCode::NOT_FOUND == res.status() {
+            return Ok(());
+        }
+
         if StatusCode::NO_CONTENT != res.status() {
             throw!(DashError::Misc(Some(res.text()?)))
         }
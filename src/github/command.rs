@@ -1,5 +1,9 @@
 use std::collections::BTreeSet;
 use std::fmt;
+use std::ops::Range;
+
+use chrono::Duration;
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
 
 use crate::config::RFC_BOT_MENTIONS;
 use crate::error::{DashError, DashResult};
@@ -102,6 +106,275 @@ impl FcpDispositionData<'_> {
     }
 }
 
+const MERGE_VERBS: &[&str] = &["merge", "merged", "merging", "merges"];
+const CLOSE_VERBS: &[&str] = &["close", "closed", "closing", "closes"];
+const POSTPONE_VERBS: &[&str] = &["postpone", "postponed", "postponing", "postpones"];
+const CANCEL_VERBS: &[&str] = &["cancel", "canceled", "canceling", "cancels"];
+const REVIEW_VERBS: &[&str] = &["reviewed", "review", "reviewing", "reviews"];
+const UNREVIEW_VERBS: &[&str] = &["unreview", "unreviewed", "unreviewing", "unreviews"];
+const ABSTAIN_VERBS: &[&str] = &["abstain", "abstained", "abstaining", "abstains"];
+const DELEGATE_VERBS: &[&str] = &["delegate", "delegated", "delegating", "delegates"];
+const ADD_TEAM_VERBS: &[&str] = &["add-team", "add-teams"];
+const REMOVE_TEAM_VERBS: &[&str] = &["remove-team", "remove-teams"];
+const EXTEND_VERBS: &[&str] = &["extend", "extended", "extending", "extends"];
+const HOLD_VERBS: &[&str] = &["hold", "held", "holding", "holds"];
+const UNHOLD_VERBS: &[&str] = &["unhold", "unheld", "unholding", "unholds"];
+const CONCERN_VERBS: &[&str] = &["concern", "concerned", "concerning", "concerns"];
+const RESOLVE_VERBS: &[&str] = &["resolve", "resolved", "resolving", "resolves"];
+const RESOLVE_FOR_VERBS: &[&str] = &["resolve-for", "resolved-for"];
+#[rustfmt::skip]
+const POLL_VERBS: &[&str] = &[
+    "poll", "polled", "polling", "polls",
+    "ask", "asked", "asking", "asks",
+    "query", "queried", "querying", "queries",
+    "inquire", "inquired", "inquiring", "inquires",
+    "quiz", "quizzed", "quizzing", "quizzes",
+    "survey", "surveyed", "surveying", "surveys",
+];
+const STATUS_VERBS: &[&str] = &["status"];
+const HELP_VERBS: &[&str] = &["help"];
+const FEEDBACK_VERBS: &[&str] = &["f?"];
+
+/// Words which can introduce a subcommand, as in `@rfcbot fcp merge`.
+const FCP_PREFIXES: &[&str] = &["fcp", "pr"];
+
+/// A subcommand, as described by `@rfcbot help`.
+struct Subcommand {
+    /// The verbs which invoke it. The first is the usual one.
+    verbs: &'static [&'static str],
+    params: &'static str,
+    description: &'static str,
+}
+
+const SUBCOMMANDS: &[Subcommand] = &[
+    Subcommand {
+        verbs: MERGE_VERBS,
+        params: "[TEAMS]",
+        description: "Propose to merge this. TEAMS is a comma separated list of the teams to \
+                      review it, which defaults to the teams labeled on the issue.",
+    },
+    Subcommand {
+        verbs: CLOSE_VERBS,
+        params: "",
+        description: "Propose to close this.",
+    },
+    Subcommand {
+        verbs: POSTPONE_VERBS,
+        params: "",
+        description: "Propose to postpone this.",
+    },
+    Subcommand {
+        verbs: CANCEL_VERBS,
+        params: "",
+        description: "Cancel the current proposal.",
+    },
+    Subcommand {
+        verbs: REVIEW_VERBS,
+        params: "",
+        description: "Mark the current proposal as reviewed by you.",
+    },
+    Subcommand {
+        verbs: UNREVIEW_VERBS,
+        params: "",
+        description: "Withdraw your review of the current proposal, until its final comment \
+                      period starts.",
+    },
+    Subcommand {
+        verbs: ABSTAIN_VERBS,
+        params: "[REASON]",
+        description: "Abstain from reviewing the current proposal, optionally saying why. \
+                      Abstentions don't count towards the quorum.",
+    },
+    Subcommand {
+        verbs: DELEGATE_VERBS,
+        params: "@USER",
+        description: "Hand your review of the current proposal to another member of your team.",
+    },
+    Subcommand {
+        verbs: ADD_TEAM_VERBS,
+        params: "TEAMS",
+        description: "Ask more teams to review the current proposal, until its final comment \
+                      period starts. TEAMS is a comma separated list.",
+    },
+    Subcommand {
+        verbs: REMOVE_TEAM_VERBS,
+        params: "TEAMS",
+        description: "Stop asking some teams to review the current proposal, until its final \
+                      comment period starts. TEAMS is a comma separated list.",
+    },
+    Subcommand {
+        verbs: EXTEND_VERBS,
+        params: "DURATION",
+        description: "Extend the running final comment period by a number of hours, days or \
+                      weeks, like `7 days`. A negative DURATION shortens it.",
+    },
+    Subcommand {
+        verbs: HOLD_VERBS,
+        params: "REASON",
+        description: "Put the current proposal on hold, which stops the clock on its final \
+                      comment period, or keeps it from starting, until it is taken off hold.",
+    },
+    Subcommand {
+        verbs: UNHOLD_VERBS,
+        params: "",
+        description: "Take the current proposal off hold. The final comment period picks up \
+                      where it left off.",
+    },
+    Subcommand {
+        verbs: CONCERN_VERBS,
+        params: "NAME",
+        description: "Raise a concern, which keeps the final comment period from starting \
+                      until it is resolved.",
+    },
+    Subcommand {
+        verbs: RESOLVE_VERBS,
+        params: "NAME",
+        description: "Resolve a concern you raised. NAME can also be the concern's number in \
+                      the status comment.",
+    },
+    Subcommand {
+        verbs: RESOLVE_FOR_VERBS,
+        params: "@USER NAME",
+        description: "Resolve a concern someone else raised, such as someone who has left the \
+                      team. Team leads can always do this, other team members only if the \
+                      repository allows it.",
+    },
+    Subcommand {
+        verbs: POLL_VERBS,
+        params: "[TEAMS] QUESTION",
+        description: "Ask the members of some teams a question, which defaults to the teams \
+                      labeled on the issue.",
+    },
+    Subcommand {
+        verbs: STATUS_VERBS,
+        params: "",
+        description: "Summarize the current proposal and polls.",
+    },
+    Subcommand {
+        verbs: HELP_VERBS,
+        params: "",
+        description: "List the commands I accept.",
+    },
+];
+
+/// A reference of every command, generated from the verbs the parser accepts.
+pub fn help() -> String {
+    fn row(msg: &mut String, verbs: &[&str], params: &str, description: &str) {
+        msg.push_str("| `");
+        msg.push_str(verbs[0]);
+        if !params.is_empty() {
+            msg.push(' ');
+            msg.push_str(params);
+        }
+        msg.push_str("` | ");
+        let aliases = verbs[1..]
+            .iter()
+            .map(|verb| format!("`{}`", verb))
+            .collect::<Vec<_>>();
+        msg.push_str(&aliases.join(", "));
+        msg.push_str(" | ");
+        msg.push_str(description);
+        msg.push_str(" |\n");
+    }
+
+    let mut msg = String::from("These are the commands I accept. Each goes on its own line ");
+    msg.push_str("after a mention of me, like `");
+    msg.push_str(RFC_BOT_MENTIONS[0]);
+    msg.push_str(" fcp merge`, and any of them except `");
+    msg.push_str(FEEDBACK_VERBS[0]);
+    msg.push_str("` can be prefixed with ");
+    let prefixes = FCP_PREFIXES
+        .iter()
+        .map(|prefix| format!("`{}`", prefix))
+        .collect::<Vec<_>>();
+    msg.push_str(&prefixes.join(" or "));
+    msg.push_str(".\n\n");
+
+    msg.push_str("| Command | Also accepted | |\n");
+    msg.push_str("| --- | --- | --- |\n");
+    for subcommand in SUBCOMMANDS {
+        row(
+            &mut msg,
+            subcommand.verbs,
+            subcommand.params,
+            subcommand.description,
+        );
+    }
+    row(
+        &mut msg,
+        FEEDBACK_VERBS,
+        "@USER",
+        "Ask someone who isn't reviewing for feedback.",
+    );
+    msg
+}
+
+/// The number of single character insertions, deletions and substitutions needed to turn one
+/// string into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Pick the candidate closest to a mistyped word, if any is close enough to be what was meant.
+pub(super) fn closest<'a, T>(
+    word: &str,
+    candidates: impl Iterator<Item = (&'a str, T)>,
+) -> Option<T> {
+    let word = word.to_lowercase();
+    let max_distance = if word.chars().count() > 4 { 2 } else { 1 };
+    candidates
+        .map(|(candidate, value)| (edit_distance(&word, &candidate.to_lowercase()), value))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, value)| value)
+}
+
+/// Suggest the subcommand a mistyped one was meant to be.
+fn suggest_subcommand(subcommand: &str) -> Option<&'static str> {
+    closest(
+        subcommand,
+        SUBCOMMANDS.iter().flat_map(|subcommand| {
+            let verbs = subcommand.verbs;
+            verbs.iter().map(move |&verb| (verb, verbs[0]))
+        }),
+    )
+}
+
+/// Suggest the team a mistyped team name was meant to be.
+fn suggest_team<'a>(setup: &'a RfcbotConfig, team_candidate: &str) -> Option<&'a str> {
+    let candidates = setup.teams().flat_map(|(label, team)| {
+        let name = strip_prefix(&label.0, "T-");
+        vec![(name, name), (team.ping(), name)]
+    });
+    closest(
+        strip_prefix(strip_prefix(team_candidate, "@"), "T-"),
+        candidates,
+    )
+}
+
+/// Append a "did you mean" to an error message if there's a suggestion.
+pub(super) fn did_you_mean(mut message: String, suggestion: Option<&str>) -> String {
+    if let Some(suggestion) = suggestion {
+        message.push_str(&format!(" Did you mean `{}`?", suggestion));
+    }
+    message
+}
+
 /// Parses the text of a subcommand.
 fn parse_command_text<'a>(command: &'a str, subcommand: &'a str) -> &'a str {
     let name_start = command.find(subcommand).unwrap() + subcommand.len();
@@ -129,6 +402,56 @@ fn match_team_candidate<'a>(
         .map(|(label, _)| label)
 }
 
+/// Parses a duration like `5 days`, `2w` or `-12 hours`. A bare number is a number of days.
+fn parse_duration(text: &str) -> Option<Duration> {
+    let unit_start = text
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || (i == 0 && (c == '-' || c == '+'))))
+        .map_or(text.len(), |(i, _)| i);
+    let amount = text[..unit_start]
+        .parse::<i64>()
+        .ok()
+        .filter(|&amount| amount != 0 && amount.abs() < 10_000)?;
+    match text[unit_start..].trim().trim_end_matches('.') {
+        "h" | "hour" | "hours" => Some(Duration::hours(amount)),
+        "" | "d" | "day" | "days" => Some(Duration::days(amount)),
+        "w" | "week" | "weeks" => Some(Duration::weeks(amount)),
+        _ => None,
+    }
+}
+
+/// Parses the name of a concern, leaving out any punctuation ending the sentence it's in.
+fn parse_concern_name(text: &str) -> &str {
+    text.trim_end_matches(|c: char| ".,;:!?".contains(c))
+        .trim_end()
+}
+
+/// The form of a concern's name used to compare it with others, so that `Foo-Bar`, `foo bar` and
+/// `foo_bar` are the same concern.
+pub fn concern_key(name: &str) -> String {
+    parse_concern_name(name.trim())
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Parses a comma separated list of teams into their labels.
+fn parse_team_list<'a>(setup: &'a RfcbotConfig, team_text: &str) -> DashResult<BTreeSet<&'a str>> {
+    let mut teams = BTreeSet::new();
+    for team_candidate in team_text.split(',').filter(|s| !s.is_empty()) {
+        let Some(team) = match_team_candidate(setup, team_candidate) else {
+            return Err(DashError::CommentableError(did_you_mean(
+                format!("Provided team `{}` is invalid.", team_candidate),
+                suggest_team(setup, team_candidate),
+            )));
+        };
+        teams.insert(&*team.0);
+    }
+    Ok(teams)
+}
+
 /// Parses all subcommands under the fcp command.
 /// If `fcp_context` is set to false, `@rfcbot <subcommand>`
 /// was passed and not `@rfcbot fcp <subcommand>`.
@@ -140,14 +463,25 @@ fn match_team_candidate<'a>(
 /// postpone ::= "postpone" | "postponed" | "postponing" | "postpones" ;
 /// cancel ::= "cancel | "canceled" | "canceling" | "cancels" ;
 /// review ::= "reviewed" | "review" | "reviewing" | "reviews" ;
+/// unreview ::= "unreview" | "unreviewed" | "unreviewing" | "unreviews" ;
+/// abstain ::= "abstain" | "abstained" | "abstaining" | "abstains" ;
+/// delegate ::= "delegate" | "delegated" | "delegating" | "delegates" ;
+/// add_team ::= "add-team" | "add-teams" ;
+/// remove_team ::= "remove-team" | "remove-teams" ;
+/// extend ::= "extend" | "extended" | "extending" | "extends" ;
+/// hold ::= "hold" | "held" | "holding" | "holds" ;
+/// unhold ::= "unhold" | "unheld" | "unholding" | "unholds" ;
 /// concern ::= "concern" | "concerned" | "concerning" | "concerns" ;
 /// resolve ::= "resolve" | "resolved" | "resolving" | "resolves" ;
+/// resolve_for ::= "resolve-for" | "resolved-for" ;
 /// poll ::=  "ask" | "asked" | "asking" | "asks" |
 ///          "poll" | "polled" | "polling" | "polls" |
 ///          "query" | "queried" | "querying" | "queries" |
 ///          "inquire" | "inquired" | "inquiring" | "inquires" |
 ///          "quiz" | "quizzed" | "quizzing" | "quizzes" |
 ///          "survey" | "surveyed" | "surveying" | "surveys" ;
+/// status ::= "status" ;
+/// help ::= "help" ;
 ///
 /// team_label ::= "T-lang" | .. ;
 /// team_label_simple ::= "lang" | .. ;
@@ -156,14 +490,23 @@ fn match_team_candidate<'a>(
 /// team_target ::= team_label | team_label_simple | team_ping ;
 /// team_list ::= team_label_any (',' team_label_any)*
 ///
+/// duration ::= ["-"] digit+ ["h" | "hours" | "d" | "days" | "w" | "weeks" | ..] ;
 /// line_remainder ::= .+$ ;
 /// ws_separated ::= ... ;
 ///
 /// subcommand ::= merge [team_list]
-///              | close | postpone | cancel | review
+///              | close | postpone | cancel | review | unreview
+///              | abstain [line_remainder]
+///              | delegate ws_separated
+///              | add_team team_list
+///              | remove_team team_list
+///              | extend duration
+///              | hold line_remainder | unhold
 ///              | concern line_remainder
 ///              | resolve line_remainder
+///              | resolve_for ws_separated line_remainder
 ///              | poll [team_target]* line_remainder
+///              | status | help
 ///              ;
 ///
 /// invocation ::= "fcp" subcommand
@@ -181,61 +524,136 @@ fn parse_fcp_subcommand<'a>(
 ) -> DashResult<RfcBotCommand<'a>> {
     Ok(match subcommand {
         // Parse a FCP merge command:
-        "merge" | "merged" | "merging" | "merges" => {
+        _ if MERGE_VERBS.contains(&subcommand) => {
             debug!("Parsed command as FcpPropose(Merge(..))");
 
             let team_text = parse_command_text(command, subcommand);
+            let teams = parse_team_list(setup, team_text)?;
 
-            let mut teams = BTreeSet::new();
-            for team_candidate in team_text.split(",").filter(|s| !s.is_empty()) {
-                let Some(team) = match_team_candidate(setup, team_candidate) else {
-                    return Err(DashError::CommentableError(format!("Provided team `{}` is invalid", team_candidate)));
-                };
-                teams.insert(&*team.0);
-            }
-
-            let teams = if teams.is_empty() {
-                None
-            } else {
-                Some(teams)
-            };
+            let teams = if teams.is_empty() { None } else { Some(teams) };
 
             RfcBotCommand::FcpPropose(FcpDispositionData::Merge(teams))
         }
 
         // Parse a FCP close command:
-        "close" | "closed" | "closing" | "closes" => {
+        _ if CLOSE_VERBS.contains(&subcommand) => {
             RfcBotCommand::FcpPropose(FcpDispositionData::Close)
         }
 
         // Parse a FCP postpone command:
-        "postpone" | "postponed" | "postponing" | "postpones" => {
+        _ if POSTPONE_VERBS.contains(&subcommand) => {
             RfcBotCommand::FcpPropose(FcpDispositionData::Postpone)
         }
 
         // Parse a FCP cancel command:
-        "cancel" | "canceled" | "canceling" | "cancels" => RfcBotCommand::FcpCancel,
+        _ if CANCEL_VERBS.contains(&subcommand) => RfcBotCommand::FcpCancel,
 
         // Parse a FCP reviewed command:
-        "reviewed" | "review" | "reviewing" | "reviews" => RfcBotCommand::Reviewed,
+        _ if REVIEW_VERBS.contains(&subcommand) => RfcBotCommand::Reviewed,
+
+        // Parse a FCP unreview command:
+        _ if UNREVIEW_VERBS.contains(&subcommand) => RfcBotCommand::Unreview,
+
+        // Parse a FCP abstain command:
+        _ if ABSTAIN_VERBS.contains(&subcommand) => {
+            debug!("Parsed command as Abstain");
+            let reason = parse_command_text(command, subcommand);
+            RfcBotCommand::Abstain(Some(reason).filter(|reason| !reason.is_empty()))
+        }
+
+        // Parse a FCP add-team or remove-team command:
+        _ if ADD_TEAM_VERBS.contains(&subcommand) || REMOVE_TEAM_VERBS.contains(&subcommand) => {
+            debug!("Parsed command as AddTeams or RemoveTeams");
+            let teams = parse_team_list(setup, parse_command_text(command, subcommand))?;
+            if teams.is_empty() {
+                throw!(DashError::CommentableError(format!(
+                    "Must specify the teams for `{}`.",
+                    subcommand
+                )));
+            }
+            if ADD_TEAM_VERBS.contains(&subcommand) {
+                RfcBotCommand::AddTeams(teams)
+            } else {
+                RfcBotCommand::RemoveTeams(teams)
+            }
+        }
+
+        // Parse a FCP extend command:
+        _ if EXTEND_VERBS.contains(&subcommand) => {
+            debug!("Parsed command as FcpExtend");
+            let duration =
+                parse_duration(parse_command_text(command, subcommand)).ok_or_else(|| {
+                    DashError::CommentableError(
+                    "Must specify how long to extend the final comment period by, like `7 days`, \
+                     or `-2 days` to shorten it."
+                        .to_string(),
+                )
+                })?;
+            RfcBotCommand::FcpExtend(duration)
+        }
+
+        // Parse a FCP hold command:
+        _ if HOLD_VERBS.contains(&subcommand) => {
+            debug!("Parsed command as Hold");
+            let reason = parse_command_text(command, subcommand);
+            if reason.is_empty() {
+                throw!(DashError::CommentableError(
+                    "Must give a reason for the hold.".to_string()
+                ));
+            }
+            RfcBotCommand::Hold(reason)
+        }
+
+        // Parse a FCP unhold command:
+        _ if UNHOLD_VERBS.contains(&subcommand) => RfcBotCommand::Unhold,
+
+        // Parse a FCP delegate command:
+        _ if DELEGATE_VERBS.contains(&subcommand) => {
+            debug!("Parsed command as Delegate");
+            let user = parse_command_text(command, subcommand)
+                .split_whitespace()
+                .next()
+                .map(|user| user.trim_start_matches('@'))
+                .filter(|user| !user.is_empty())
+                .ok_or_else(|| {
+                    DashError::CommentableError(
+                        "Must specify who to delegate the review to.".to_string(),
+                    )
+                })?;
+            RfcBotCommand::Delegate(user)
+        }
 
         // Parse a FCP concern command:
-        "concern" | "concerned" | "concerning" | "concerns" => {
+        _ if CONCERN_VERBS.contains(&subcommand) => {
             debug!("Parsed command as NewConcern");
-            RfcBotCommand::NewConcern(parse_command_text(command, subcommand))
+            let concern_name = parse_concern_name(parse_command_text(command, subcommand));
+            RfcBotCommand::NewConcern(concern_name)
         }
 
         // Parse a FCP resolve command:
-        "resolve" | "resolved" | "resolving" | "resolves" => {
+        _ if RESOLVE_VERBS.contains(&subcommand) => {
             debug!("Parsed command as ResolveConcern");
-            RfcBotCommand::ResolveConcern(parse_command_text(command, subcommand))
+            let concern_name = parse_concern_name(parse_command_text(command, subcommand));
+            RfcBotCommand::ResolveConcern(concern_name)
+        }
+
+        // Parse a FCP resolve-for command:
+        _ if RESOLVE_FOR_VERBS.contains(&subcommand) => {
+            debug!("Parsed command as ResolveConcernFor");
+            let text = parse_command_text(command, subcommand);
+            let user = text.split_whitespace().next().unwrap_or("");
+            let concern = parse_concern_name(parse_command_text(text, user));
+            let user = user.trim_start_matches('@');
+            if user.is_empty() || concern.is_empty() {
+                throw!(DashError::CommentableError(
+                    "Must specify whose concern to resolve and its name.".to_string()
+                ));
+            }
+            RfcBotCommand::ResolveConcernFor { user, concern }
         }
 
         // Parse a StartPoll command:
-        "ask" | "asked" | "asking" | "asks" | "poll" | "polled" | "polling" | "polls" | "query"
-        | "queried" | "querying" | "queries" | "inquire" | "inquired" | "inquiring"
-        | "inquires" | "quiz" | "quizzed" | "quizzing" | "quizzes" | "survey" | "surveyed"
-        | "surveying" | "surveys" => {
+        _ if POLL_VERBS.contains(&subcommand) => {
             debug!("Parsed command as StartPoll");
 
             let mut question = parse_command_text(command, subcommand);
@@ -251,12 +669,29 @@ fn parse_fcp_subcommand<'a>(
             RfcBotCommand::StartPoll { teams, question }
         }
 
-        _ => throw!(DashError::Misc(if fcp_context {
-            error!("unrecognized subcommand for fcp: {}", subcommand);
-            Some(format!("found bad subcommand: {}", subcommand))
-        } else {
-            None
-        })),
+        // Parse a status command:
+        _ if STATUS_VERBS.contains(&subcommand) => RfcBotCommand::Status,
+
+        // Parse a help command:
+        _ if HELP_VERBS.contains(&subcommand) => RfcBotCommand::Help,
+
+        _ => {
+            let suggestion = suggest_subcommand(subcommand);
+            if fcp_context {
+                throw!(DashError::CommentableError(did_you_mean(
+                    format!("Unknown subcommand `{}` for `fcp`.", subcommand),
+                    suggestion,
+                )));
+            } else if suggestion.is_some() {
+                throw!(DashError::CommentableError(did_you_mean(
+                    format!("Unknown command `{}`.", subcommand),
+                    suggestion,
+                )));
+            } else {
+                // might just be someone talking about the bot
+                throw!(DashError::Misc(None))
+            }
+        }
     })
 }
 
@@ -276,14 +711,14 @@ fn from_invocation_line<'a>(
         .split_whitespace();
     let invocation = tokens.next().ok_or(DashError::Misc(None))?;
     match invocation {
-        "fcp" | "pr" => {
+        _ if FCP_PREFIXES.contains(&invocation) => {
             let subcommand = tokens.next().ok_or(DashError::Misc(None))?;
 
             debug!("Parsed command as new FCP proposal");
 
             parse_fcp_subcommand(setup, command, subcommand, true)
         }
-        "f?" => {
+        _ if FEEDBACK_VERBS.contains(&invocation) => {
             let user = tokens
                 .next()
                 .ok_or_else(|| DashError::Misc(Some("no user specified".to_string())))?;
@@ -303,36 +738,115 @@ pub enum RfcBotCommand<'a> {
     FcpPropose(FcpDispositionData<'a>),
     FcpCancel,
     Reviewed,
+    Unreview,
+    Abstain(Option<&'a str>),
+    Delegate(&'a str),
+    AddTeams(BTreeSet<&'a str>),
+    RemoveTeams(BTreeSet<&'a str>),
+    FcpExtend(Duration),
+    Hold(&'a str),
+    Unhold,
     NewConcern(&'a str),
     ResolveConcern(&'a str),
+    ResolveConcernFor {
+        user: &'a str,
+        concern: &'a str,
+    },
     FeedbackRequest(&'a str),
     StartPoll {
         teams: BTreeSet<&'a str>,
         question: &'a str,
     },
+    Status,
+    Help,
 }
 
 impl<'a> RfcBotCommand<'a> {
+    /// The usual way of invoking the command, for messages about it.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RfcBotCommand::FcpPropose(FcpDispositionData::Merge(_)) => "fcp merge",
+            RfcBotCommand::FcpPropose(FcpDispositionData::Close) => "fcp close",
+            RfcBotCommand::FcpPropose(FcpDispositionData::Postpone) => "fcp postpone",
+            RfcBotCommand::FcpCancel => "fcp cancel",
+            RfcBotCommand::Reviewed => "reviewed",
+            RfcBotCommand::Unreview => "unreview",
+            RfcBotCommand::Abstain(_) => "abstain",
+            RfcBotCommand::Delegate(_) => "delegate",
+            RfcBotCommand::AddTeams(_) => "fcp add-team",
+            RfcBotCommand::RemoveTeams(_) => "fcp remove-team",
+            RfcBotCommand::FcpExtend(_) => "fcp extend",
+            RfcBotCommand::Hold(_) => "hold",
+            RfcBotCommand::Unhold => "unhold",
+            RfcBotCommand::NewConcern(_) => "concern",
+            RfcBotCommand::ResolveConcern(_) => "resolve",
+            RfcBotCommand::ResolveConcernFor { .. } => "resolve-for",
+            RfcBotCommand::FeedbackRequest(_) => "f?",
+            RfcBotCommand::StartPoll { .. } => "poll",
+            RfcBotCommand::Status => "status",
+            RfcBotCommand::Help => "help",
+        }
+    }
+
     pub fn from_str_all(
         setup: &'a RfcbotConfig,
         command: &'a str,
     ) -> impl Iterator<Item = DashResult<RfcBotCommand<'a>>> {
+        let ignored = ignored_ranges(command);
+
         // Get the tokens for each command line (starts with a bot mention)
-        command
-            .lines()
-            .map(str::trim)
-            .filter(|&l| RFC_BOT_MENTIONS.iter().any(|m| l.starts_with(m)))
-            .map(move |l| from_invocation_line(setup, l))
+        command.lines().filter_map(move |l| {
+            let start =
+                l.as_ptr() as usize - command.as_ptr() as usize + l.len() - l.trim_start().len();
+            let l = l.trim();
+            if !RFC_BOT_MENTIONS.iter().any(|m| l.starts_with(m)) {
+                return None;
+            }
+
+            let ignored_by = ignored
+                .iter()
+                .filter(|(range, _)| range.contains(&start))
+                .collect::<Vec<_>>();
+            if ignored_by.is_empty() {
+                Some(from_invocation_line(setup, l))
+            } else if ignored_by.iter().all(|&&(_, quote)| quote) {
+                // Markdown continues a quote onto the line after it, which is easy to miss
+                Some(Err(DashError::CommentableError(format!(
+                    "I ignored `{}`, as it continues the quote above it. Leave a blank line after \
+                     a quote for me to see a command.",
+                    l
+                ))))
+            } else {
+                None
+            }
+        })
     }
 }
 
+/// The parts of a comment's Markdown where commands don't count: fenced code blocks, inline code
+/// and block quotes, which are mostly quoting someone else. Indented code blocks aren't included,
+/// as commands are often indented by accident. Block quotes are marked `true`.
+fn ignored_ranges(markdown: &str) -> Vec<(Range<usize>, bool)> {
+    Parser::new(markdown)
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::Start(Tag::BlockQuote) => Some((range, true)),
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(_))) | Event::Code(_) => {
+                Some((range, false))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::teams::test::TEST_SETUP;
 
     fn parse_commands(body: &str) -> impl Iterator<Item = RfcBotCommand<'_>> {
-        RfcBotCommand::from_str_all(&TEST_SETUP, body).map(|c| c.expect("No errors expected in tests."))
+        RfcBotCommand::from_str_all(&TEST_SETUP, body)
+            .map(|c| c.expect("No errors expected in tests."))
     }
 
     #[test]
@@ -359,6 +873,58 @@ foobar
         );
     }
 
+    #[test]
+    fn command_names() {
+        let text = r#"
+@rfcbot fcp merge
+@rfcbot fcp close
+@rfcbot fcp postpone
+@rfcbot fcp cancel
+@rfcbot reviewed
+@rfcbot unreview
+@rfcbot abstain
+@rfcbot delegate @bar
+@rfcbot fcp add-team avengers
+@rfcbot fcp remove-team avengers
+@rfcbot fcp extend 7 days
+@rfcbot hold waiting for the survey
+@rfcbot unhold
+@rfcbot concern foo
+@rfcbot resolve foo
+@rfcbot resolve-for @bar foo
+@rfcbot f? @bar
+@rfcbot poll Is this a question?
+@rfcbot status
+@rfcbot help
+"#;
+
+        assert_eq!(
+            parse_commands(text).map(|c| c.name()).collect::<Vec<_>>(),
+            vec![
+                "fcp merge",
+                "fcp close",
+                "fcp postpone",
+                "fcp cancel",
+                "reviewed",
+                "unreview",
+                "abstain",
+                "delegate",
+                "fcp add-team",
+                "fcp remove-team",
+                "fcp extend",
+                "hold",
+                "unhold",
+                "concern",
+                "resolve",
+                "resolve-for",
+                "f?",
+                "poll",
+                "status",
+                "help",
+            ]
+        );
+    }
+
     #[test]
     fn accept_leading_whitespace() {
         let text = r#"
@@ -474,6 +1040,155 @@ somemoretext"
         RfcBotCommand::Reviewed
     );
 
+    test_from_str!(
+        success_fcp_unreview,
+        [
+            "unreview",
+            "unreviewed",
+            "unreviewing",
+            "unreviews",
+            "fcp unreview",
+            "fcp unreviewed",
+            "pr unreview",
+            "pr unreviewed"
+        ],
+        RfcBotCommand::Unreview
+    );
+
+    test_from_str!(
+        success_abstain,
+        ["abstain", "abstained", "fcp abstain", "pr abstaining"],
+        RfcBotCommand::Abstain(None)
+    );
+
+    test_from_str!(
+        success_abstain_reason,
+        ["abstain", "abstains", "fcp abstain", "pr abstained"],
+        some_text!("I co-wrote the RFC"),
+        RfcBotCommand::Abstain(Some("I co-wrote the RFC"))
+    );
+
+    test_from_str!(
+        success_delegate,
+        ["delegate", "delegates", "fcp delegate", "pr delegated"],
+        some_text!("@bors"),
+        RfcBotCommand::Delegate("bors")
+    );
+
+    test_from_str!(
+        success_add_teams,
+        ["add-team", "fcp add-team", "pr add-teams"],
+        some_text!("avengers,T-justice-league"),
+        RfcBotCommand::AddTeams(btreeset! {"T-avengers", "justice-league"})
+    );
+
+    test_from_str!(
+        success_remove_teams,
+        ["remove-team", "fcp remove-team", "pr remove-teams"],
+        some_text!("@marvel/avengers"),
+        RfcBotCommand::RemoveTeams(btreeset! {"T-avengers"})
+    );
+
+    #[test]
+    fn change_teams_requires_teams() {
+        assert_eq!(
+            parse_error("@rfcbot fcp add-team").as_deref(),
+            Some("Must specify the teams for `add-team`.")
+        );
+        assert_eq!(
+            parse_error("@rfcbot fcp remove-team avenger").as_deref(),
+            Some("Provided team `avenger` is invalid. Did you mean `avengers`?")
+        );
+    }
+
+    test_from_str!(
+        success_extend_days,
+        ["extend", "fcp extend", "pr extended"],
+        some_text!("5 days"),
+        RfcBotCommand::FcpExtend(Duration::days(5))
+    );
+
+    test_from_str!(
+        success_extend_weeks,
+        ["fcp extend"],
+        some_text!("2w"),
+        RfcBotCommand::FcpExtend(Duration::weeks(2))
+    );
+
+    test_from_str!(
+        success_shorten,
+        ["fcp extend"],
+        some_text!("-12 hours"),
+        RfcBotCommand::FcpExtend(Duration::hours(-12))
+    );
+
+    #[test]
+    fn extend_requires_duration() {
+        for body in &[
+            "@rfcbot fcp extend",
+            "@rfcbot fcp extend a while",
+            "@rfcbot fcp extend 0 days",
+            "@rfcbot fcp extend 3 fortnights",
+        ] {
+            assert_eq!(
+                parse_error(body).as_deref(),
+                Some(
+                    "Must specify how long to extend the final comment period by, like \
+                     `7 days`, or `-2 days` to shorten it."
+                ),
+            );
+        }
+    }
+
+    test_from_str!(
+        success_hold,
+        ["hold", "fcp hold", "pr holding"],
+        some_text!("waiting for the survey results"),
+        RfcBotCommand::Hold("waiting for the survey results")
+    );
+
+    test_from_str!(
+        success_unhold,
+        ["unhold", "fcp unhold", "pr unheld"],
+        RfcBotCommand::Unhold
+    );
+
+    #[test]
+    fn hold_requires_reason() {
+        assert_eq!(
+            parse_error("@rfcbot fcp hold").as_deref(),
+            Some("Must give a reason for the hold.")
+        );
+    }
+
+    test_from_str!(
+        success_resolve_for,
+        ["resolve-for", "fcp resolve-for", "pr resolved-for"],
+        some_text!("@bors CONCERN_NAME"),
+        RfcBotCommand::ResolveConcernFor {
+            user: "bors",
+            concern: "CONCERN_NAME",
+        }
+    );
+
+    #[test]
+    fn resolve_for_requires_user_and_concern() {
+        for body in &["@rfcbot resolve-for", "@rfcbot resolve-for @bors"] {
+            assert_eq!(
+                parse_error(body).as_deref(),
+                Some("Must specify whose concern to resolve and its name.")
+            );
+        }
+    }
+
+    #[test]
+    fn delegate_requires_user() {
+        assert_eq!(
+            parse_error("@rfcbot delegate").as_deref(),
+            Some("Must specify who to delegate the review to.")
+        );
+    }
+
     test_from_str!(
         success_fcp_merge,
         [
@@ -511,7 +1226,9 @@ somemoretext"
             "pr merges compiler,lang"
         ],
         justification!(),
-        RfcBotCommand::FcpPropose(FcpDispositionData::Merge(Some(["compiler", "lang"].iter().copied().collect())))
+        RfcBotCommand::FcpPropose(FcpDispositionData::Merge(Some(
+            ["compiler", "lang"].iter().copied().collect()
+        )))
     );
 
     test_from_str!(
@@ -614,6 +1331,26 @@ somemoretext"
         RfcBotCommand::ResolveConcern("CONCERN_NAME")
     );
 
+    #[test]
+    fn concern_name_punctuation() {
+        let text = "@rfcbot concern CONCERN_NAME.\n@rfcbot resolve CONCERN_NAME!\n";
+        assert_eq!(
+            parse_commands(text).collect::<Vec<_>>(),
+            vec![
+                RfcBotCommand::NewConcern("CONCERN_NAME"),
+                RfcBotCommand::ResolveConcern("CONCERN_NAME"),
+            ]
+        );
+    }
+
+    #[test]
+    fn concern_keys() {
+        assert_eq!(concern_key("Foo-Bar"), "foo-bar");
+        assert_eq!(concern_key("foo bar"), "foo-bar");
+        assert_eq!(concern_key("  foo__bar?"), "foo-bar");
+        assert_ne!(concern_key("foo bar"), concern_key("foobar"));
+    }
+
     test_from_str!(
         success_ask_question,
         [
@@ -700,6 +1437,29 @@ somemoretext"
         }
     );
 
+    test_from_str!(
+        success_status,
+        ["status", "fcp status", "pr status"],
+        RfcBotCommand::Status
+    );
+
+    test_from_str!(
+        success_help,
+        ["help", "fcp help", "pr help"],
+        RfcBotCommand::Help
+    );
+
+    #[test]
+    fn help_lists_every_verb() {
+        let help = help();
+        let verbs = SUBCOMMANDS.iter().map(|s| s.verbs);
+        for verb in verbs.chain(Some(FEEDBACK_VERBS)).flatten() {
+            assert!(help.contains(&format!("`{}", verb)), "missing {}", verb);
+        }
+        assert!(help.contains("| `merge [TEAMS]` | `merged`, `merging`, `merges` | "));
+        assert!(help.contains("| `status` |  | "));
+    }
+
     #[test]
     fn success_resolve_mid_body() {
         let body = "someothertext
@@ -734,4 +1494,145 @@ somemoretext";
         let with_alias = ensure_take_singleton(parse_commands(body));
         assert_eq!(with_alias, RfcBotCommand::FcpCancel);
     }
+
+    #[test]
+    fn ignore_fenced_code() {
+        let text = r#"
+To cancel, say:
+
+```
+@rfcbot fcp cancel
+```
+
+~~~markdown
+@rfcbot concern in-tildes
+~~~
+
+@rfcbot reviewed
+"#;
+
+        assert_eq!(
+            parse_commands(text).collect::<Vec<_>>(),
+            vec![RfcBotCommand::Reviewed]
+        );
+    }
+
+    #[test]
+    fn ignore_block_quotes() {
+        let text = r#"
+> @rfcbot fcp cancel
+>
+> nested:
+> > @rfcbot concern quoted
+
+>@rfcbot concern no-space
+
+@rfcbot concern foobar
+"#;
+
+        assert_eq!(
+            parse_commands(text).collect::<Vec<_>>(),
+            vec![RfcBotCommand::NewConcern("foobar")]
+        );
+    }
+
+    #[test]
+    fn reply_to_command_continuing_a_quote() {
+        let text = r#"
+> > nested, and
+> > after the quote, without a blank line
+@rfcbot concern lazy
+"#;
+
+        assert_eq!(
+            parse_error(text).as_deref(),
+            Some(
+                "I ignored `@rfcbot concern lazy`, as it continues the quote above it. Leave a \
+                 blank line after a quote for me to see a command."
+            )
+        );
+    }
+
+    #[test]
+    fn ignore_inline_code() {
+        let text = r#"
+Someone wrote `@rfcbot fcp merge` earlier, and `this code span
+@rfcbot fcp cancel` spans lines.
+
+@rfcbot fcp close
+"#;
+
+        assert_eq!(
+            parse_commands(text).collect::<Vec<_>>(),
+            vec![RfcBotCommand::FcpPropose(FcpDispositionData::Close)]
+        );
+    }
+
+    #[test]
+    fn accept_indented_command() {
+        let text = "Some text.\n\n    @rfcbot reviewed\n";
+
+        assert_eq!(
+            parse_commands(text).collect::<Vec<_>>(),
+            vec![RfcBotCommand::Reviewed]
+        );
+    }
+
+    fn parse_error(body: &str) -> Option<String> {
+        match RfcBotCommand::from_str_all(&TEST_SETUP, body).next() {
+            Some(Err(DashError::CommentableError(message))) => Some(message),
+            Some(Err(_)) => None,
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn suggest_mistyped_subcommand() {
+        assert_eq!(
+            parse_error("@rfcbot fcp mrege").as_deref(),
+            Some("Unknown subcommand `mrege` for `fcp`. Did you mean `merge`?")
+        );
+        assert_eq!(
+            parse_error("@rfcbot fcp frobnicate").as_deref(),
+            Some("Unknown subcommand `frobnicate` for `fcp`.")
+        );
+        assert_eq!(
+            parse_error("@rfcbot reviwed").as_deref(),
+            Some("Unknown command `reviwed`. Did you mean `reviewed`?")
+        );
+        assert_eq!(
+            parse_error("@rfcbot concerrned foo").as_deref(),
+            Some("Unknown command `concerrned`. Did you mean `concern`?")
+        );
+    }
+
+    #[test]
+    fn ignore_unrecognized_mention() {
+        assert_eq!(parse_error("@rfcbot is great"), None);
+    }
+
+    #[test]
+    fn suggest_mistyped_team() {
+        assert_eq!(
+            parse_error("@rfcbot fcp merge avenger").as_deref(),
+            Some("Provided team `avenger` is invalid. Did you mean `avengers`?")
+        );
+        assert_eq!(
+            parse_error("@rfcbot fcp merge T-justice-leage").as_deref(),
+            Some("Provided team `T-justice-leage` is invalid. Did you mean `justice-league`?")
+        );
+        assert_eq!(
+            parse_error("@rfcbot fcp merge xmen").as_deref(),
+            Some("Provided team `xmen` is invalid.")
+        );
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("merge", "merge"), 0);
+        assert_eq!(edit_distance("mrege", "merge"), 2);
+        assert_eq!(edit_distance("merg", "merge"), 1);
+        assert_eq!(edit_distance("", "poll"), 4);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
 }
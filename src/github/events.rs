@@ -0,0 +1,192 @@
+//! An append-only log of what has happened to each FCP proposal.
+//!
+//! Cancelling a proposal deletes it along with its concerns and review requests, so the log is
+//! the only record of proposals which didn't make it. Events aren't recorded while replaying
+//! comments: they were recorded the first time round.
+
+use chrono::Utc;
+use diesel::prelude::*;
+
+use super::command::FcpDisposition;
+use super::nag::Context;
+use crate::domain::rfcbot::{FcpProposal, NewFcpEvent};
+use crate::domain::schema::fcp_event;
+use crate::error::DashResult;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Event<'a> {
+    Proposed(FcpDisposition),
+    DispositionChanged(FcpDisposition),
+    Reviewed,
+    Unreviewed,
+    Abstained(Option<&'a str>),
+    Delegated(&'a str),
+    TeamAdded(&'a str),
+    TeamRemoved(&'a str),
+    ConcernRaised(&'a str),
+    ConcernResolved(&'a str),
+    /// A concern resolved by someone other than whoever raised it.
+    ConcernResolvedFor(&'a str),
+    ConcernWithdrawn(&'a str),
+    Started,
+    Extended,
+    Held(&'a str),
+    Unheld,
+    Paused,
+    Finished,
+    Cancelled,
+}
+
+impl Event<'_> {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Event::Proposed(_) => "proposed",
+            Event::DispositionChanged(_) => "disposition-changed",
+            Event::Reviewed => "reviewed",
+            Event::Unreviewed => "unreviewed",
+            Event::Abstained(_) => "abstained",
+            Event::Delegated(_) => "delegated",
+            Event::TeamAdded(_) => "team-added",
+            Event::TeamRemoved(_) => "team-removed",
+            Event::ConcernRaised(_) => "concern-raised",
+            Event::ConcernResolved(_) => "concern-resolved",
+            Event::ConcernResolvedFor(_) => "concern-resolved-for",
+            Event::ConcernWithdrawn(_) => "concern-withdrawn",
+            Event::Started => "started",
+            Event::Extended => "extended",
+            Event::Held(_) => "held",
+            Event::Unheld => "unheld",
+            Event::Paused => "paused",
+            Event::Finished => "finished",
+            Event::Cancelled => "cancelled",
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match *self {
+            Event::Proposed(disposition) | Event::DispositionChanged(disposition) => {
+                Some(disposition.repr())
+            }
+            Event::Abstained(reason) => reason,
+            Event::Held(reason) => Some(reason),
+            Event::Delegated(name)
+            | Event::TeamAdded(name)
+            | Event::TeamRemoved(name)
+            | Event::ConcernRaised(name)
+            | Event::ConcernResolved(name)
+            | Event::ConcernResolvedFor(name)
+            | Event::ConcernWithdrawn(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Record something happening to a proposal. `actor` is the user responsible, if any, and
+/// `comment` the comment where it happened.
+pub(super) fn record(
+    ctx: &Context<'_>,
+    proposal: &FcpProposal,
+    event: Event<'_>,
+    actor: Option<i32>,
+    comment: Option<i64>,
+) -> DashResult<()> {
+    if ctx.is_replaying() {
+        return Ok(());
+    }
+
+    let new_event = NewFcpEvent {
+        fk_proposal: proposal.id,
+        fk_issue: proposal.fk_issue,
+        event: event.as_str(),
+        detail: event.detail(),
+        fk_actor: actor,
+        fk_comment: comment,
+        created_at: Utc::now().naive_utc(),
+    };
+    diesel::insert_into(fcp_event::table)
+        .values(&new_event)
+        .execute(ctx.conn)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::schema::fcp_proposal;
+    use crate::github::tests::{
+        test_comment, test_connection, test_issue, test_proposal, test_user,
+    };
+
+    #[test]
+    fn events_outlive_a_cancelled_proposal() {
+        let conn = &test_connection();
+        let ctx = &Context::new(conn);
+        let alice = test_user(conn, -101, "alice");
+        let bob = test_user(conn, -102, "bob");
+        let issue = test_issue(conn, 1, true, &[]);
+        let proposal = test_proposal(conn, &issue, &alice);
+
+        let proposed = Event::Proposed(FcpDisposition::Merge);
+        record(
+            ctx,
+            &proposal,
+            proposed,
+            Some(alice.id),
+            Some(proposal.fk_initiating_comment),
+        )
+        .unwrap();
+        let reviewed = test_comment(conn, &issue, &bob, "@rfcbot reviewed");
+        record(
+            ctx,
+            &proposal,
+            Event::Reviewed,
+            Some(bob.id),
+            Some(reviewed.id),
+        )
+        .unwrap();
+        let cancelled = test_comment(conn, &issue, &alice, "@rfcbot cancel");
+        diesel::delete(fcp_proposal::table.find(proposal.id))
+            .execute(conn)
+            .unwrap();
+        record(
+            ctx,
+            &proposal,
+            Event::Cancelled,
+            Some(alice.id),
+            Some(cancelled.id),
+        )
+        .unwrap();
+
+        // what `/api/fcp/<id>/events` responds with
+        let events = crate::nag::load_fcp_events(conn, proposal.id).unwrap();
+        let summary = events
+            .iter()
+            .map(|e| {
+                (
+                    e.event.event.as_str(),
+                    e.event.detail.as_deref(),
+                    e.actor.as_ref().map(|a| a.login.as_str()),
+                    e.event.fk_comment,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "proposed",
+                    Some("merge"),
+                    Some("alice"),
+                    Some(proposal.fk_initiating_comment)
+                ),
+                ("reviewed", None, Some("bob"), Some(reviewed.id)),
+                ("cancelled", None, Some("alice"), Some(cancelled.id)),
+            ]
+        );
+
+        let json = serde_json::to_value(&events).unwrap();
+        assert_eq!(json[0]["event"]["event"], "proposed");
+        assert_eq!(json[1]["actor"]["login"], "bob");
+    }
+}
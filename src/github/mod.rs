@@ -2,10 +2,16 @@
 
 pub mod client;
 mod command;
+mod events;
 pub mod models;
 mod nag;
+pub mod outbox;
+pub mod reconcile;
+pub mod replay;
 pub mod webhooks;
 
+use std::convert::TryFrom;
+
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
@@ -18,6 +24,8 @@ use crate::DB_POOL;
 use self::client::Client;
 use self::models::{CommentFromJson, IssueFromJson, PullRequestFromJson};
 
+pub use self::nag::start_evaluator_thread;
+
 lazy_static! {
     pub static ref GH: Client = Client::new();
 }
@@ -106,7 +114,7 @@ pub fn ingest_since(repo: &str, start: DateTime<Utc>) -> DashResult<()> {
     // insert the comments
     for comment in comments {
         let comment_id = comment.id;
-        ok_or!(handle_comment(conn, comment, repo), why =>
+        ok_or!(handle_comment(conn, comment, repo, None), why =>
             error!("Error processing comment {}#{}: {:?}",
                    repo, comment_id, why));
     }
@@ -138,27 +146,40 @@ pub fn handle_pr(conn: &PgConnection, pr: PullRequestFromJson, repo: &str) -> Da
     Ok(())
 }
 
-pub fn handle_comment(conn: &PgConnection, comment: CommentFromJson, repo: &str) -> DashResult<()> {
+/// Store a comment and process its commands. `sender` is who made the change when a webhook tells
+/// us, which matters for edits: the bot edits its own status comments too.
+pub fn handle_comment(
+    conn: &PgConnection,
+    comment: CommentFromJson,
+    repo: &str,
+    sender: Option<&GitHubUser>,
+) -> DashResult<()> {
     handle_user(conn, &comment.user)?;
 
     let comment: IssueComment = comment.with_repo(repo)?;
 
-    // We only want to run `nag::update_nags` on insert to avoid
-    // double-processing commits, so we can't use upsert here
-    if issuecomment::table
+    // Edits only have the commands they change processed, to avoid
+    // double-processing comments, so we can't use upsert here
+    if let Ok(previous) = issuecomment::table
         .find(comment.id)
         .get_result::<IssueComment>(conn)
-        .is_ok()
     {
         diesel::update(issuecomment::table.find(comment.id))
             .set(&comment)
             .execute(conn)?;
+
+        if previous.body != comment.body {
+            ok_or!(nag::update_nags_for_edit(conn, &previous, &comment, sender), why => {
+                error!("Problem updating FCPs: {:?}", &why);
+                throw!(why);
+            });
+        }
     } else {
         diesel::insert_into(issuecomment::table)
             .values(&comment)
             .execute(conn)?;
 
-        ok_or!(nag::update_nags(&comment), why => {
+        ok_or!(nag::update_nags(conn, &comment), why => {
             error!("Problem updating FCPs: {:?}", &why);
             throw!(why);
         });
@@ -167,6 +188,45 @@ pub fn handle_comment(conn: &PgConnection, comment: CommentFromJson, repo: &str)
     Ok(())
 }
 
+/// Forget about a comment which has been deleted on GitHub.
+pub fn handle_deleted_comment(conn: &PgConnection, comment: CommentFromJson) -> DashResult<()> {
+    let comment_id = i64::try_from(comment.id).expect("id fits into i64");
+
+    let comment = issuecomment::table
+        .find(comment_id)
+        .first::<IssueComment>(conn)
+        .optional()?;
+
+    if let Some(comment) = comment {
+        ok_or!(nag::delete_comment(conn, &comment), why => {
+            error!("Problem deleting comment {}: {:?}", comment.id, &why);
+            throw!(why);
+        });
+    }
+
+    Ok(())
+}
+
+pub fn handle_label(
+    conn: &PgConnection,
+    repo: &str,
+    number: i32,
+    label: &str,
+    added: bool,
+) -> DashResult<()> {
+    let issue = issue::table
+        .filter(issue::repository.eq(repo))
+        .filter(issue::number.eq(number))
+        .first::<Issue>(conn)?;
+
+    ok_or!(nag::update_nags_for_label(conn, &issue, label, added), why => {
+        error!("Problem updating FCP teams for {}#{}: {:?}", repo, number, &why);
+        throw!(why);
+    });
+
+    Ok(())
+}
+
 pub fn handle_issue(conn: &PgConnection, issue: IssueFromJson, repo: &str) -> DashResult<()> {
     // user handling
     handle_user(conn, &issue.user)?;
@@ -214,10 +274,151 @@ pub fn handle_user(conn: &PgConnection, user: &GitHubUser) -> DashResult<()> {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::domain::rfcbot::{FcpProposal, NewFcpProposal};
     use std::env;
 
+    /// Connect to the test database. Nothing done through the connection is committed.
+    pub(crate) fn test_connection() -> PgConnection {
+        crate::utils::setup_test_env();
+        let db_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let conn = PgConnection::establish(&db_url)
+            .unwrap_or_else(|_| panic!("Error connecting to {}", db_url));
+        conn.begin_test_transaction()
+            .expect("Unable to start a test transaction");
+        conn
+    }
+
+    /// Insert a made up user to test with.
+    pub(crate) fn test_user(conn: &PgConnection, id: i32, login: &str) -> GitHubUser {
+        let user = GitHubUser {
+            id,
+            login: login.to_string(),
+        };
+        handle_user(conn, &user).expect("Unable to insert the test user");
+        user
+    }
+
+    /// Insert an issue to test against, opened by a made up user.
+    pub(crate) fn test_issue(
+        conn: &PgConnection,
+        number: i32,
+        open: bool,
+        labels: &[&str],
+    ) -> Issue {
+        let user = test_user(conn, -100, "test-author");
+
+        let now = Utc::now().naive_utc();
+        let issue = IssuePartial {
+            number,
+            fk_milestone: None,
+            fk_user: user.id,
+            fk_assignee: None,
+            open,
+            is_pull_request: false,
+            title: "Test issue".to_string(),
+            body: String::new(),
+            locked: false,
+            closed_at: None,
+            created_at: now,
+            updated_at: now,
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            repository: "rust-lang/test".to_string(),
+        };
+        let id = diesel::insert_into(issue::table)
+            .values(&issue)
+            .returning(issue::id)
+            .get_result(conn)
+            .expect("Unable to insert the test issue");
+        issue.complete(id)
+    }
+
+    /// Insert a pending proposal to merge a test issue, along with the comments proposing it.
+    pub(crate) fn test_proposal(
+        conn: &PgConnection,
+        issue: &Issue,
+        initiator: &GitHubUser,
+    ) -> FcpProposal {
+        let proposing = test_comment(conn, issue, initiator, "@rfcbot merge");
+        let status = test_comment(conn, issue, initiator, "Team member has proposed to merge");
+        diesel::insert_into(fcp_proposal::table)
+            .values(&NewFcpProposal {
+                id: None,
+                fk_issue: issue.id,
+                fk_initiator: initiator.id,
+                fk_initiating_comment: proposing.id,
+                disposition: "merge",
+                fk_bot_tracking_comment: status.id,
+                fcp_start: None,
+                fcp_closed: false,
+            })
+            .get_result(conn)
+            .expect("Unable to insert the test proposal")
+    }
+
+    /// An open issue on which alice has proposed to merge, for testing what happens to a proposal.
+    pub(crate) struct Fixture {
+        pub(crate) conn: PgConnection,
+        pub(crate) alice: GitHubUser,
+        pub(crate) issue: Issue,
+        pub(crate) proposal: FcpProposal,
+    }
+
+    impl Fixture {
+        pub(crate) fn new() -> Self {
+            let conn = test_connection();
+            let alice = test_user(&conn, -101, "alice");
+            let issue = test_issue(&conn, 1, true, &[]);
+            let proposal = test_proposal(&conn, &issue, &alice);
+            Fixture {
+                conn,
+                alice,
+                issue,
+                proposal,
+            }
+        }
+
+        pub(super) fn ctx(&self) -> nag::Context<'_> { nag::Context::new(&self.conn) }
+
+        /// Insert another user, with an id counting down from alice's.
+        pub(crate) fn user(&self, id: i32, login: &str) -> GitHubUser {
+            test_user(&self.conn, id, login)
+        }
+
+        /// Insert a comment on the issue.
+        pub(crate) fn comment(&self, author: &GitHubUser, body: &str) -> IssueComment {
+            test_comment(&self.conn, &self.issue, author, body)
+        }
+    }
+
+    /// Insert a comment on a test issue. Comment ids come from GitHub, so they're made up too.
+    pub(crate) fn test_comment(
+        conn: &PgConnection,
+        issue: &Issue,
+        author: &GitHubUser,
+        body: &str,
+    ) -> IssueComment {
+        use std::sync::atomic::{AtomicI64, Ordering};
+        static NEXT_ID: AtomicI64 = AtomicI64::new(-1);
+
+        let now = Utc::now().naive_utc();
+        let comment = IssueComment {
+            id: NEXT_ID.fetch_sub(1, Ordering::SeqCst),
+            fk_issue: issue.id,
+            fk_user: author.id,
+            body: body.to_string(),
+            created_at: now,
+            updated_at: now,
+            repository: issue.repository.clone(),
+        };
+        diesel::insert_into(issuecomment::table)
+            .values(&comment)
+            .execute(conn)
+            .expect("Unable to insert the test comment");
+        comment
+    }
+
     #[test]
     fn test_handle_user() {
         crate::utils::setup_test_env();
@@ -47,7 +47,7 @@ impl MilestoneFromJson {
 
 #[derive(Debug, Deserialize)]
 pub struct LabelFromJson {
-    name: String,
+    pub name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,13 +1,15 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryFrom;
 use std::sync::Mutex;
 
-use chrono::{Duration, Utc};
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::pg::PgConnection;
 use diesel::prelude::*;
 
 use itertools::Itertools;
 
 use super::GH;
+use crate::config::RFC_BOT_MENTIONS;
 use crate::domain::github::{GitHubUser, Issue, IssueComment};
 use crate::domain::rfcbot::{
     FcpConcern, FcpProposal, FcpReviewRequest, FeedbackRequest, NewFcpConcern, NewFcpProposal,
@@ -19,11 +21,14 @@ use crate::domain::schema::{
     poll_response_request,
 };
 use crate::error::{DashError, DashResult};
+use crate::github::events::{self, Event};
 use crate::github::models::CommentFromJson;
-use crate::teams::SETUP;
+use crate::github::outbox::{self, Action};
+use crate::github::replay;
+use crate::teams::{QuorumPolicy, ResolveForPolicy, RfcbotConfig, SETUP};
 use crate::DB_POOL;
 
-use crate::github::command::{FcpDisposition, FcpDispositionData, Label, RfcBotCommand};
+use crate::github::command::{self, FcpDisposition, FcpDispositionData, Label, RfcBotCommand};
 
 impl Issue {
     fn remove_label(&self, label: Label) {
@@ -33,23 +38,226 @@ impl Issue {
     fn add_label(&self, label: Label) -> DashResult<()> {
         GH.add_label(&self.repository, self.number, label.as_str())
     }
+}
+
+lazy_static! {
+    pub(super) static ref NAG_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// What FCP and poll state changes are made with: the connection to make them through, and
+/// whether they're being made by replaying an issue's comments (see `replay`).
+#[derive(Clone, Copy)]
+pub(super) struct Context<'a> {
+    pub(super) conn: &'a PgConnection,
+    replay: Option<Replay<'a>>,
+}
+
+/// The issue being replayed, and when the comment being replayed was made.
+#[derive(Clone, Copy)]
+struct Replay<'a> {
+    issue: i32,
+    clock: NaiveDateTime,
+    /// The ids the issue's proposals had before replaying, by the comment which made them.
+    proposal_ids: &'a BTreeMap<i64, i32>,
+}
+
+impl<'a> Context<'a> {
+    pub(super) fn new(conn: &'a PgConnection) -> Self { Context { conn, replay: None } }
+
+    /// Replay a comment on an issue which was made at `clock`. Only the issue's state is
+    /// evaluated, and nothing is sent to GitHub, which has seen it all the first time round.
+    pub(super) fn replay(
+        conn: &'a PgConnection,
+        issue: &Issue,
+        clock: NaiveDateTime,
+        proposal_ids: &'a BTreeMap<i64, i32>,
+    ) -> Self {
+        Context {
+            conn,
+            replay: Some(Replay {
+                issue: issue.id,
+                clock,
+                proposal_ids,
+            }),
+        }
+    }
+
+    pub(super) fn is_replaying(&self) -> bool { self.replay.is_some() }
+
+    /// The current time, or while replaying, the time the replayed comment was made.
+    pub(super) fn now(&self) -> NaiveDateTime {
+        self.replay
+            .map_or_else(|| Utc::now().naive_utc(), |replay| replay.clock)
+    }
 
-    fn close(&self) {
-        ok_or!(GH.close_issue(&self.repository, self.number), why =>
-            error!("Unable to close issue {:?}: {:?}", self, why));
+    /// Should state belonging to this issue be evaluated? While replaying, only the replayed
+    /// issue's is.
+    pub(super) fn in_scope(&self, issue_id: i32) -> bool {
+        self.replay.map_or(true, |replay| replay.issue == issue_id)
+    }
+
+    /// While replaying, the id the proposal made by this comment had before, so that it keeps its
+    /// id and with it its events.
+    fn proposal_id(&self, initiating_comment: i64) -> Option<i32> {
+        self.replay
+            .and_then(|replay| replay.proposal_ids.get(&initiating_comment).copied())
+    }
+
+    /// Was the issue open at `now()`? Issues which have been reopened count as open throughout.
+    pub(super) fn is_open(&self, issue: &Issue) -> bool {
+        issue.open
+            || issue
+                .closed_at
+                .map_or(false, |closed_at| closed_at > self.now())
+    }
+
+    /// Queue actions in the outbox, within the transaction making the change they announce. While
+    /// replaying, GitHub has seen them already.
+    fn enqueue(&self, issue: &Issue, actions: &[Action<'_>]) -> DashResult<()> {
+        if self.is_replaying() {
+            return Ok(());
+        }
+        outbox::enqueue(self.conn, issue, actions)
     }
 }
 
-lazy_static! {
-    static ref NAG_LOCK: Mutex<()> = Mutex::new(());
+const EVALUATE_NAGS_EVERY_MIN: u64 = 30;
+
+/// Periodically evaluate all pending proposals, running FCPs and open polls, so that an FCP
+/// finishes on time even if nobody comments anywhere in the meantime.
+pub fn start_evaluator_thread() {
+    let _ = crate::utils::spawn_scheduled_thread("nag evaluator", EVALUATE_NAGS_EVERY_MIN, || {
+        let _in_progress_marker = NAG_LOCK.lock();
+        let conn = &*DB_POOL.get()?;
+        let ctx = &Context::new(conn);
+        evaluate_nags(ctx);
+        next_fcp_deadline(ctx)
+    });
+}
+
+pub fn update_nags(conn: &PgConnection, comment: &IssueComment) -> DashResult<()> {
+    let _in_progress_marker = NAG_LOCK.lock();
+    let ctx = &Context::new(conn);
+
+    process_comment(ctx, comment, None)?;
+    evaluate_nags(ctx);
+
+    Ok(())
+}
+
+/// Apply an edit to a comment: commands added by the edit are processed, and concerns removed by
+/// it are withdrawn. Ticking or unticking a box in a status comment reviews or withdraws a review,
+/// unless `sender` says the bot made the edit.
+pub fn update_nags_for_edit(
+    conn: &PgConnection,
+    previous: &IssueComment,
+    comment: &IssueComment,
+    sender: Option<&GitHubUser>,
+) -> DashResult<()> {
+    let _in_progress_marker = NAG_LOCK.lock();
+    let ctx = &Context::new(conn);
+
+    apply_ticky_box_edits(ctx, previous, comment, sender)?;
+    process_comment(ctx, comment, Some(&previous.body))?;
+    evaluate_nags(ctx);
+
+    Ok(())
 }
 
-// TODO check if new subteam label added for existing proposals
+/// Keep the teams reviewing an issue's pending proposal in line with its team labels: adding a team
+/// label asks that team to review it too, and removing one stops asking them.
+pub fn update_nags_for_label(
+    conn: &PgConnection,
+    issue: &Issue,
+    label: &str,
+    added: bool,
+) -> DashResult<()> {
+    let _in_progress_marker = NAG_LOCK.lock();
+    let ctx = &Context::new(conn);
+
+    let is_team = SETUP
+        .read()
+        .unwrap()
+        .teams()
+        .any(|(team, _)| team.0 == label);
+    let proposal = match existing_proposal(ctx, issue)? {
+        Some(proposal) if is_team && proposal.fcp_start.is_none() => proposal,
+        _ => return Ok(()),
+    };
+
+    let teams = std::iter::once(label).collect::<BTreeSet<_>>();
+    let changed = conn.transaction::<_, DashError, _>(|| {
+        if added {
+            add_review_teams(ctx, &proposal, &teams, None, None)
+        } else {
+            remove_review_teams(ctx, &proposal, &teams, None, None)
+        }
+    });
+    // there's no comment to reply to, so the last team keeps reviewing and the issue is told why
+    if let Err(DashError::CommentableError(why)) = changed {
+        info!(
+            "Not removing {} from {}#{}: {}",
+            label, issue.repository, issue.number, why
+        );
+        let messages = [format!(
+            "The `{}` label was removed, but that team is still reviewing the proposal. {}",
+            label, why
+        )];
+        let reply = RfcBotComment::new(
+            issue,
+            CommentType::Error {
+                messages: &messages,
+            },
+        );
+        return ctx.enqueue(issue, &[Action::Comment(&reply.body)]);
+    }
+    changed?;
+    evaluate_nags(ctx);
+
+    Ok(())
+}
 
-pub fn update_nags(comment: &IssueComment) -> DashResult<()> {
+/// Undo what a comment which has been deleted on GitHub did, and forget about it: concerns it
+/// raised are withdrawn, and if it was the status comment of a proposal or a poll, the status
+/// comment is posted again.
+pub fn delete_comment(conn: &PgConnection, comment: &IssueComment) -> DashResult<()> {
     let _in_progress_marker = NAG_LOCK.lock();
+    let ctx = &Context::new(conn);
+
+    forget_comment(ctx, comment)?;
+    evaluate_nags(ctx);
+
+    Ok(())
+}
+
+/// Everything `delete_comment` does except evaluating the resulting state.
+fn forget_comment(ctx: &Context<'_>, comment: &IssueComment) -> DashResult<()> {
+    let conn = ctx.conn;
+
+    let issue = issue::table.find(comment.fk_issue).first::<Issue>(conn)?;
+
+    withdraw_concerns(ctx, &issue, comment, |_| true)?;
+    repost_status_comments(ctx, &issue, comment)?;
+
+    // the status comment of a finished FCP isn't posted again, so it's still needed
+    let finished_status = fcp_proposal::table
+        .filter(fcp_proposal::fk_bot_tracking_comment.eq(comment.id))
+        .filter(fcp_proposal::fcp_closed.eq(true));
+    if !diesel::select(diesel::dsl::exists(finished_status)).get_result::<bool>(conn)? {
+        diesel::delete(issuecomment::table.find(comment.id)).execute(conn)?;
+    }
+
+    Ok(())
+}
 
-    let conn = &*DB_POOL.get()?;
+/// Process the commands in a comment, without evaluating the resulting state. For an edited
+/// comment, `previous_body` is what it said before. The caller must hold `NAG_LOCK`.
+pub(super) fn process_comment(
+    ctx: &Context<'_>,
+    comment: &IssueComment,
+    previous_body: Option<&str>,
+) -> DashResult<()> {
+    let conn = ctx.conn;
 
     let issue = issue::table.find(comment.fk_issue).first::<Issue>(conn)?;
 
@@ -57,94 +265,209 @@ pub fn update_nags(comment: &IssueComment) -> DashResult<()> {
         .find(comment.fk_user)
         .first::<GitHubUser>(conn)?;
 
-    let subteam_members = subteam_members(&issue)?;
-    let all_team_members = all_team_members()?;
+    let subteam_members = subteam_members(ctx, &issue)?;
+    let all_team_members = all_team_members(ctx)?;
+    let issue_teams = all_teams_for_issue(&issue);
 
-    // Attempt to parse all commands out of the comment
-    let mut any = false;
     let teams = SETUP.read().unwrap();
-    for command in RfcBotCommand::from_str_all(&teams, &comment.body) {
+    let edited = edited_commands(&teams, previous_body, &comment.body);
+
+    // concerns which an edit has removed are withdrawn
+    for concern_name in edited.removed_concerns {
+        let key = command::concern_key(concern_name);
+        let withdrawn = |c: &FcpConcern| {
+            command::concern_key(&c.name) == key && c.fk_resolved_comment.is_none()
+        };
+        ok_or!(withdraw_concerns(ctx, &issue, comment, withdrawn), why =>
+            error!("Unable to withdraw concern {} for comment id {}: {:?}",
+                concern_name, comment.id, why));
+    }
+
+    // problems with the comment's commands are collected and reported in a single reply
+    let mut errors = Vec::new();
+    let mut refused = Vec::new();
+
+    let mut any = false;
+    for command in edited.commands {
         let command = match command {
-            Err(DashError::CommentableError(ref message)) => {
-                let comment = RfcBotComment::new(&issue, CommentType::Error { message });
-                comment.post(None)?;
+            Ok(command) => command,
+            Err(message) => {
+                errors.push(message);
                 continue;
             }
-            Err(_) => continue,
-            Ok(command) => command,
         };
 
         any = true;
 
-        if let RfcBotCommand::StartPoll { .. }
-        | RfcBotCommand::FcpPropose(FcpDispositionData::Merge(Some(_))) = command
-        {
-            // Accept poll requests and "fcp merge team" from any known user.
-            if all_team_members.iter().find(|&u| u == &author).is_none() {
-                // FIXME: post an error message in the issue.
-                info!("poll requester ({}) is not a known user", author.login);
-                return Ok(());
-            }
+        // Accept poll requests and "fcp merge team" from any known user, but most bot commands
+        // only from members of the subteams labeled on the issue. Anyone can ask for the status or
+        // for help.
+        let any_team = matches!(
+            command,
+            RfcBotCommand::StartPoll { .. }
+                | RfcBotCommand::FcpPropose(FcpDispositionData::Merge(Some(_)))
+                | RfcBotCommand::Delegate(_)
+        );
+        let allowed = if any_team {
+            &all_team_members
         } else {
-            // Don't accept most bot commands from non-subteam members.
-            // Early return because we'll just get here again...
-            if subteam_members.iter().find(|&u| u == &author).is_none() {
-                // FIXME: post an error message in the issue.
-                info!(
-                    "command author ({}) doesn't appear in any relevant subteams",
-                    author.login
-                );
-                return Ok(());
-            }
+            &subteam_members
+        };
+        let public = matches!(command, RfcBotCommand::Status | RfcBotCommand::Help);
+        if !public && !allowed.contains(&author) {
+            info!(
+                "command author ({}) isn't allowed to use {}",
+                author.login,
+                command.name()
+            );
+            refused.push((command.name(), any_team));
+            continue;
         }
 
         // For `fcp merge`, if specific teams were passed, then be sure to use
-        // only those members.
+        // only those teams.
         let team_members = match &command {
             RfcBotCommand::FcpPropose(FcpDispositionData::Merge(None)) => {
-                let issue_teams = all_teams_for_issue(&issue);
                 if issue_teams.len() > 1 {
-                    return Err(DashError::CommentableError(
+                    errors.push(
                         "Must specify teams for FCP, as there are multiple labeled.".to_string(),
-                    ));
+                    );
+                    continue;
                 }
-                subteam_members.clone()
+                subteam_reviewers(ctx, &issue)?
             }
             RfcBotCommand::FcpPropose(FcpDispositionData::Merge(Some(teams))) => {
-                specific_subteam_members(|label| {
+                specific_subteam_reviewers(ctx, |label| {
                     teams.iter().any(|team| {
                         label.strip_prefix("T-").unwrap_or(label)
                             == team.strip_prefix("T-").unwrap_or(team)
                     })
                 })?
             }
-            _ => subteam_members.clone(),
+            RfcBotCommand::FcpPropose(_) => subteam_reviewers(ctx, &issue)?,
+            _ => Vec::new(),
         };
 
         debug!("processing rfcbot command: {:?}", &command);
-        let process = command.process(&author, &issue, comment, &team_members);
-        ok_or!(process, why => {
-            error!("Unable to process command for comment id {}: {:?}",
-                comment.id, why);
-            return Ok(());
-        });
+        match command.process(ctx, &author, &issue, comment, &team_members) {
+            Ok(()) => debug!("rfcbot command is processed"),
+            Err(DashError::CommentableError(message)) => errors.push(message),
+            Err(why) => error!(
+                "Unable to process command for comment id {}: {:?}",
+                comment.id, why
+            ),
+        }
+    }
 
-        debug!("rfcbot command is processed");
+    let mut replies = Vec::new();
+    if !errors.is_empty() {
+        replies.push(CommentType::Error { messages: &errors });
+    }
+    replies.extend(
+        refused
+            .into_iter()
+            .map(|(command, any_team)| CommentType::Unauthorized {
+                author: &author,
+                command,
+                teams: if any_team { None } else { Some(&issue_teams) },
+            }),
+    );
+    let reply = if replies.len() > 1 {
+        Some(CommentType::Combined(replies))
+    } else {
+        replies.pop()
+    };
+    if let Some(reply) = reply {
+        if let Err(why) = RfcBotComment::new(&issue, reply).post(ctx, None) {
+            warn!("Unable to reply to comment {}: {:?}", comment.id, why);
+        }
     }
 
-    if !any {
-        ok_or!(resolve_applicable_feedback_requests(&author, &issue, comment),
+    // an edit doesn't count as feedback, the comment did when it was made
+    if !any && previous_body.is_none() {
+        ok_or!(resolve_applicable_feedback_requests(ctx, &author, &issue, comment),
             why => error!("Unable to resolve feedback requests for comment id {}: {:?}",
                         comment.id, why));
     }
 
-    evaluate_nags();
-
     Ok(())
 }
 
-fn update_proposal_review_status(proposal_id: i32) -> DashResult<()> {
-    let conn = &*DB_POOL.get()?;
+/// What's left to do for a comment's commands, see `edited_commands`.
+struct EditedCommands<'a> {
+    /// Commands to process, or the error parsing them, in the order they appear in the comment.
+    commands: Vec<Result<RfcBotCommand<'a>, String>>,
+    /// Concerns which were raised before the edit but no longer are.
+    removed_concerns: Vec<&'a str>,
+}
+
+/// Work out which of the commands in a comment haven't been dealt with yet. For a new comment,
+/// that's all of them. For an edited comment, `previous_body` is what it said before, and the
+/// commands and errors which were in it have been dealt with already.
+fn edited_commands<'a>(
+    setup: &'a RfcbotConfig,
+    previous_body: Option<&'a str>,
+    body: &'a str,
+) -> EditedCommands<'a> {
+    let mut previous_commands = Vec::new();
+    let mut previous_errors = Vec::new();
+    for command in previous_body
+        .into_iter()
+        .flat_map(|body| RfcBotCommand::from_str_all(setup, body))
+    {
+        match command {
+            Ok(command) => previous_commands.push(command),
+            Err(DashError::CommentableError(message)) => previous_errors.push(message),
+            Err(_) => (),
+        }
+    }
+
+    // Attempt to parse all commands out of the comment
+    let parsed = RfcBotCommand::from_str_all(setup, body).collect::<Vec<_>>();
+
+    let removed_concerns = previous_commands
+        .iter()
+        .filter_map(|command| match *command {
+            RfcBotCommand::NewConcern(name) => Some(name),
+            _ => None,
+        })
+        .filter(|name| {
+            let key = command::concern_key(name);
+            !parsed.iter().any(
+                |c| matches!(c, Ok(RfcBotCommand::NewConcern(n)) if command::concern_key(n) == key),
+            )
+        })
+        .collect();
+
+    let mut commands = Vec::new();
+    for command in parsed {
+        match command {
+            Ok(command) => {
+                if let Some(i) = previous_commands.iter().position(|c| *c == command) {
+                    previous_commands.remove(i);
+                } else {
+                    commands.push(Ok(command));
+                }
+            }
+            Err(DashError::CommentableError(message)) => {
+                if let Some(i) = previous_errors.iter().position(|m| *m == message) {
+                    previous_errors.remove(i);
+                } else {
+                    commands.push(Err(message));
+                }
+            }
+            Err(_) => (),
+        }
+    }
+
+    EditedCommands {
+        commands,
+        removed_concerns,
+    }
+}
+
+fn update_proposal_review_status(ctx: &Context<'_>, proposal_id: i32) -> DashResult<()> {
+    let conn = ctx.conn;
     // this is an updated comment from the bot itself
 
     // parse out each "reviewed" status for each user, then update them
@@ -161,23 +484,14 @@ fn update_proposal_review_status(proposal_id: i32) -> DashResult<()> {
         .first(conn)?;
 
     // parse the status comment and mark any new reviews as reviewed
-    for username in parse_ticky_boxes("proposal", proposal.id, &comment) {
+    let ticked = parse_ticky_boxes("proposal", proposal.id, &comment)
+        .filter_map(|(username, reviewed)| if reviewed { Some(username) } else { None });
+    for username in ticked {
         let user: GitHubUser = githubuser::table
             .filter(githubuser::login.eq(username))
             .first(conn)?;
 
-        {
-            use crate::domain::schema::fcp_review_request::dsl::*;
-            let mut review_request: FcpReviewRequest = fcp_review_request
-                .filter(fk_proposal.eq(proposal.id))
-                .filter(fk_reviewer.eq(user.id))
-                .first(conn)?;
-
-            review_request.reviewed = true;
-            diesel::update(fcp_review_request.find(review_request.id))
-                .set(&review_request)
-                .execute(conn)?;
-        }
+        review(ctx, &proposal, &user, comment.id)?;
     }
 
     Ok(())
@@ -185,8 +499,8 @@ fn update_proposal_review_status(proposal_id: i32) -> DashResult<()> {
 
 /// Given a poll, parse out each "responded" status, in the poll's ticky boxes,
 // for each user, then update the responded status in the database.
-fn update_poll_response_status(poll_id: i32) -> DashResult<()> {
-    let conn = &*DB_POOL.get()?;
+fn update_poll_response_status(ctx: &Context<'_>, poll_id: i32) -> DashResult<()> {
+    let conn = ctx.conn;
     // this is an updated comment from the bot itself
 
     let survey: Poll = poll::table.find(poll_id).first(conn)?;
@@ -201,7 +515,9 @@ fn update_poll_response_status(poll_id: i32) -> DashResult<()> {
         .first(conn)?;
 
     // parse the status comment and mark any new responses as responded
-    for respondent in parse_ticky_boxes("poll", survey.id, &comment) {
+    let ticked = parse_ticky_boxes("poll", survey.id, &comment)
+        .filter_map(|(respondent, responded)| if responded { Some(respondent) } else { None });
+    for respondent in ticked {
         let user = githubuser::table
             .filter(githubuser::login.eq(respondent))
             .first(conn);
@@ -228,29 +544,113 @@ fn update_poll_response_status(poll_id: i32) -> DashResult<()> {
     Ok(())
 }
 
+/// Review or withdraw the reviews whose boxes an edit to a proposal's status comment has ticked or
+/// unticked, unless its FCP has started.
+fn apply_ticky_box_edits(
+    ctx: &Context<'_>,
+    previous: &IssueComment,
+    comment: &IssueComment,
+    sender: Option<&GitHubUser>,
+) -> DashResult<()> {
+    if sender.map_or(false, |sender| is_bot(&sender.login)) {
+        return Ok(());
+    }
+
+    let conn = ctx.conn;
+
+    let proposal = fcp_proposal::table
+        .filter(fcp_proposal::fk_bot_tracking_comment.eq(comment.id))
+        .first::<FcpProposal>(conn)
+        .optional()?;
+    let proposal = match proposal {
+        Some(proposal) if proposal.fcp_start.is_none() && !proposal.fcp_closed => proposal,
+        _ => return Ok(()),
+    };
+
+    let review_requests = list_review_requests(ctx, proposal.id)?;
+    let reviewed_in_db = review_requests
+        .iter()
+        .filter(|(_, request)| request.reviewed)
+        .map(|(user, _)| &*user.login)
+        .collect::<BTreeSet<_>>();
+
+    for (username, reviewed) in ticky_box_edits(proposal.id, previous, comment, &reviewed_in_db) {
+        let user = githubuser::table
+            .filter(githubuser::login.eq(username))
+            .first::<GitHubUser>(conn);
+        let user = ok_or_continue!(user, why =>
+            error!("Can't find reviewer {} in the database: {:?}", username, why));
+
+        if reviewed {
+            review(ctx, &proposal, &user, comment.id)?;
+        } else {
+            withdraw_review(ctx, &proposal, &user, comment.id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The boxes an edit to a proposal's status comment ticked or unticked, and whether they're now
+/// ticked. The stored status comment can lag behind reviews made with commands, and webhooks can
+/// arrive late, so a box only counts if the edit changed it and it disagrees with `reviewed`, the
+/// reviewers the database says have reviewed.
+fn ticky_box_edits<'a>(
+    proposal_id: i32,
+    previous: &IssueComment,
+    comment: &'a IssueComment,
+    reviewed: &BTreeSet<&str>,
+) -> Vec<(&'a str, bool)> {
+    let ticked_before = parse_ticky_boxes("proposal", proposal_id, previous)
+        .filter(|&(_, ticked)| ticked)
+        .map(|(username, _)| username)
+        .collect::<BTreeSet<_>>();
+
+    parse_ticky_boxes("proposal", proposal_id, comment)
+        .filter(|&(username, ticked)| ticked != ticked_before.contains(username))
+        .filter(|&(username, ticked)| ticked != reviewed.contains(username))
+        .collect()
+}
+
+/// Whether `login` is one of the bot's own accounts.
+fn is_bot(login: &str) -> bool {
+    RFC_BOT_MENTIONS
+        .iter()
+        .any(|mention| mention.trim_start_matches('@') == login)
+}
+
+/// How a box for a delegated review says who it was delegated to.
+const DELEGATED_TO: &str = "(delegated to @";
+
+/// Parse the ticky boxes in a status comment into who they're for and whether they're ticked.
 fn parse_ticky_boxes<'a>(
     what: &'a str,
     id: i32,
     comment: &'a IssueComment,
-) -> impl Iterator<Item = &'a str> {
+) -> impl Iterator<Item = (&'a str, bool)> {
     comment.body.lines().filter_map(move |line| {
         if line.starts_with("* [") {
             let l = line.trim_start_matches("* [");
             let reviewed = l.starts_with('x');
             let remaining = l.trim_start_matches("x] @").trim_start_matches(" ] @");
+            // a delegated box is for the reviewer it was delegated to
+            let remaining = match remaining.find(DELEGATED_TO) {
+                Some(i) => &remaining[i + DELEGATED_TO.len()..],
+                None => remaining,
+            };
 
-            if let Some(username) = remaining.split_whitespace().next() {
+            let username = remaining
+                .split(|c: char| c.is_whitespace() || c == ')')
+                .next()
+                .filter(|username| !username.is_empty());
+            if let Some(username) = username {
                 trace!(
                     "reviewer parsed as reviewed? {} (line: \"{}\")",
                     reviewed,
                     l
                 );
 
-                if reviewed {
-                    Some(username)
-                } else {
-                    None
-                }
+                Some((username, reviewed))
             } else {
                 warn!(
                     "An empty usename showed up in comment {} for {} {}",
@@ -264,22 +664,140 @@ fn parse_ticky_boxes<'a>(
     })
 }
 
-fn evaluate_nags() {
-    ok_or!(evaluate_pendings(), why =>
+pub(super) fn evaluate_nags(ctx: &Context<'_>) {
+    ok_or!(evaluate_pendings(ctx), why =>
         error!("Unable to evaluate outstanding proposals: {:?}", why));
 
-    ok_or!(evaluate_ffcps(), why =>
+    ok_or!(evaluate_ffcps(ctx), why =>
         error!("Unable to evaluate outstanding ffcps: {:?}", why));
 
-    ok_or!(evaluate_polls(), why =>
+    ok_or!(evaluate_polls(ctx), why =>
         error!("Unable to evaluate outstanding polls: {:?}", why));
 }
 
-fn evaluate_polls() -> DashResult<()> {
+/// How long an FCP lasts once it has started, going by the teams reviewing it. Review requests
+/// predating per-team reviews don't have a team, so without any teams we go by the issue's labels.
+fn fcp_length(issue: &Issue, reviews: &[(GitHubUser, FcpReviewRequest)]) -> Duration {
+    let mut teams = reviews
+        .iter()
+        .filter_map(|(_, review)| review.team_label.clone())
+        .collect::<Vec<_>>();
+    teams.sort();
+    teams.dedup();
+    if teams.is_empty() {
+        teams = issue.labels.clone();
+    }
+
+    SETUP
+        .read()
+        .unwrap()
+        .fcp_duration(&issue.repository, &teams)
+}
+
+/// When the FCP for this proposal is due to finish, if it has started. The end is stored when the
+/// FCP starts, so that changing the issue's labels doesn't move it; FCPs started before that
+/// still work it out from the labels.
+fn fcp_end(proposal: &FcpProposal, issue: &Issue) -> Option<NaiveDateTime> {
+    let start = proposal.fcp_start?;
+    Some(
+        proposal
+            .extended_end
+            .unwrap_or_else(|| start + fcp_length(issue, &[])),
+    )
+}
+
+/// The quorum policies of all the teams labeled on this issue.
+fn quorum_policies(issue: &Issue) -> Vec<QuorumPolicy> {
+    let setup = SETUP.read().unwrap();
+    let mut policies = Vec::new();
+    for (label, _) in setup.teams() {
+        let policy = setup.quorum_policy(&label.0);
+        if issue.labels.contains(&label.0) && !policies.contains(&policy) {
+            policies.push(policy);
+        }
+    }
+    if policies.is_empty() {
+        policies.push(QuorumPolicy::default());
+    }
+    policies
+}
+
+/// The quorum policies a group of reviewers has to satisfy. Review requests predating per-team
+/// reviews don't have a team, those have to satisfy the policies of all the labeled teams.
+fn group_quorum_policies(issue: &Issue, team: Option<&str>) -> Vec<QuorumPolicy> {
+    match team {
+        Some(label) => vec![SETUP.read().unwrap().quorum_policy(label)],
+        None => quorum_policies(issue),
+    }
+}
+
+/// Group review requests by the team they were made on behalf of.
+fn reviews_by_team(
+    reviews: &[(GitHubUser, FcpReviewRequest)],
+) -> BTreeMap<Option<&str>, Vec<&(GitHubUser, FcpReviewRequest)>> {
+    let mut groups = BTreeMap::new();
+    for review in reviews {
+        groups
+            .entry(review.1.team_label.as_deref())
+            .or_insert_with(Vec::new)
+            .push(review);
+    }
+    groups
+}
+
+/// Has every team reviewing the proposal reached its quorum?
+fn quorum_reached(issue: &Issue, reviews: &[(GitHubUser, FcpReviewRequest)]) -> bool {
+    let mut groups = reviews_by_team(reviews);
+    if groups.is_empty() {
+        // nobody to review, which none of the policies should consider a quorum
+        groups.insert(None, Vec::new());
+    }
+
+    groups.into_iter().all(|(team, group)| {
+        let (complete, outstanding) = review_counts(&group);
+        group_quorum_policies(issue, team)
+            .into_iter()
+            .all(|policy| policy.is_met(complete, outstanding))
+    })
+}
+
+/// Count the complete and outstanding reviews of a group of reviewers. Abstentions don't count
+/// either way.
+fn review_counts(group: &[&(GitHubUser, FcpReviewRequest)]) -> (usize, usize) {
+    let outstanding = group
+        .iter()
+        .filter(|(_, r)| !r.reviewed && !r.abstained)
+        .count();
+    let complete = group.iter().filter(|(_, r)| r.reviewed).count();
+    (complete, outstanding)
+}
+
+/// Load all FCPs which have started but haven't been marked as finished yet, leaving out those
+/// whose clock is stopped by a hold.
+fn running_fcps(ctx: &Context<'_>) -> DashResult<Vec<(FcpProposal, Issue)>> {
+    let conn = ctx.conn;
+
+    Ok(fcp_proposal::table
+        .inner_join(issue::table)
+        .filter(fcp_proposal::fcp_start.is_not_null())
+        .filter(fcp_proposal::fcp_closed.eq(false))
+        .filter(fcp_proposal::held_at.is_null())
+        .load::<(FcpProposal, Issue)>(conn)?)
+}
+
+/// Find when the earliest running FCP is due to finish.
+fn next_fcp_deadline(ctx: &Context<'_>) -> DashResult<Option<NaiveDateTime>> {
+    Ok(running_fcps(ctx)?
+        .iter()
+        .filter_map(|(proposal, issue)| fcp_end(proposal, issue))
+        .min())
+}
+
+fn evaluate_polls(ctx: &Context<'_>) -> DashResult<()> {
     use crate::domain::schema::issuecomment::dsl::id as issuecomment_id;
     use crate::domain::schema::issuecomment::dsl::*;
     use crate::domain::schema::poll::dsl::*;
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
 
     // first process all "pending" polls (unresponded)
     let pending = poll.filter(poll_closed.eq(false)).load::<Poll>(conn);
@@ -288,7 +806,7 @@ fn evaluate_polls() -> DashResult<()> {
         throw!(why)
     });
 
-    for mut survey in pending {
+    for mut survey in pending.into_iter().filter(|s| ctx.in_scope(s.fk_issue)) {
         let initiator = githubuser::table
             .find(survey.fk_initiator)
             .first::<GitHubUser>(conn);
@@ -302,12 +820,12 @@ fn evaluate_polls() -> DashResult<()> {
                     survey.id, why));
 
         // check to see if any checkboxes were modified before we end up replacing the comment
-        ok_or_continue!(update_poll_response_status(survey.id), why =>
+        ok_or_continue!(update_poll_response_status(ctx, survey.id), why =>
             error!("Unable to update response status for poll {}: {:?}",
                     survey.id, why));
 
         // get associated responses
-        let responses = ok_or_continue!(list_poll_response_requests(survey.id), why =>
+        let responses = ok_or_continue!(list_poll_response_requests(ctx, survey.id), why =>
             error!("Unable to retrieve response requests for survey {}: {:?}",
                     survey.id, why));
 
@@ -340,7 +858,7 @@ fn evaluate_polls() -> DashResult<()> {
             // if the comment body in the database equals the new one we generated, then no change
             // is needed from github (this assumes our DB accurately reflects GH's, which should
             // be true in most cases by the time this is called)
-            let post = status_comment.post(Some(survey.fk_bot_tracking_comment));
+            let post = status_comment.post(ctx, Some(survey.fk_bot_tracking_comment));
             ok_or_continue!(post, why =>
                 error!("Unable to update status comment for poll {}: {:?}",
                         survey.id, why));
@@ -350,12 +868,12 @@ fn evaluate_polls() -> DashResult<()> {
     Ok(())
 }
 
-fn evaluate_pendings() -> DashResult<()> {
+fn evaluate_pendings(ctx: &Context<'_>) -> DashResult<()> {
     use crate::domain::schema::fcp_proposal::dsl::*;
     use crate::domain::schema::issuecomment::dsl::id as issuecomment_id;
     use crate::domain::schema::issuecomment::dsl::*;
     use diesel::prelude::*;
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
 
     // first process all "pending" proposals (unreviewed or remaining concerns)
     let pending = fcp_proposal
@@ -366,7 +884,10 @@ fn evaluate_pendings() -> DashResult<()> {
         throw!(why)
     });
 
-    for mut proposal in pending_proposals {
+    for mut proposal in pending_proposals
+        .into_iter()
+        .filter(|p| ctx.in_scope(p.fk_issue))
+    {
         let initiator = githubuser::table
             .find(proposal.fk_initiator)
             .first::<GitHubUser>(conn);
@@ -381,28 +902,37 @@ fn evaluate_pendings() -> DashResult<()> {
 
         // if the issue has been closed before an FCP starts,
         // then we just need to cancel the FCP entirely
-        if !issue.open {
-            ok_or_continue!(cancel_fcp(&initiator, &issue, &proposal), why =>
+        if !ctx.is_open(&issue) {
+            ok_or_continue!(cancel_fcp(ctx, &initiator, &issue, &proposal, None), why =>
                 error!("Unable to cancel FCP for proposal {}: {:?}",
                         proposal.id, why));
         }
 
-        // check to see if any checkboxes were modified before we end up replacing the comment
-        ok_or_continue!(update_proposal_review_status(proposal.id), why =>
-            error!("Unable to update review status for proposal {}: {:?}",
-                    proposal.id, why));
+        // Boxes ticked in the status comment are applied as the edits come in. Replaying only
+        // has the comment as it is now, so count its ticked boxes before replacing it.
+        if ctx.is_replaying() {
+            ok_or_continue!(update_proposal_review_status(ctx, proposal.id), why =>
+                error!("Unable to update review status for proposal {}: {:?}",
+                        proposal.id, why));
+        }
 
         // get associated concerns and reviews
-        let reviews = ok_or_continue!(list_review_requests(proposal.id), why =>
+        let reviews = ok_or_continue!(list_review_requests(ctx, proposal.id), why =>
             error!("Unable to retrieve review requests for proposal {}: {:?}",
                     proposal.id, why));
 
-        let concerns = ok_or_continue!(list_concerns_with_authors(proposal.id),
+        let delegators = ok_or_continue!(list_delegators(ctx, &reviews), why =>
+            error!("Unable to retrieve delegators for proposal {}: {:?}",
+                    proposal.id, why));
+
+        let concerns = ok_or_continue!(list_concerns_with_authors(ctx, proposal.id),
             why => error!("Unable to retrieve concerns for proposal {}: {:?}",
                     proposal.id, why));
 
-        let num_outstanding_reviews = reviews.iter().filter(|&&(_, ref r)| !r.reviewed).count();
-        let num_complete_reviews = reviews.len() - num_outstanding_reviews;
+        let hold = ok_or_continue!(load_hold(ctx, &proposal), why =>
+            error!("Unable to retrieve the hold on proposal {}: {:?}",
+                    proposal.id, why));
+
         let num_active_concerns = concerns
             .iter()
             .filter(|&&(_, ref c)| c.fk_resolved_comment.is_none())
@@ -416,6 +946,9 @@ fn evaluate_pendings() -> DashResult<()> {
                 FcpDisposition::from_str(&proposal.disposition)?,
                 &reviews,
                 &concerns,
+                &delegators,
+                hold.as_ref()
+                    .map(|(holder, reason)| (holder, reason.as_str())),
             ),
         );
 
@@ -423,190 +956,185 @@ fn evaluate_pendings() -> DashResult<()> {
             .filter(issuecomment_id.eq(proposal.fk_bot_tracking_comment))
             .first(conn)?;
 
-        if previous_comment.body != status_comment.body {
-            // if the comment body in the database equals the new one we generated, then no change
-            // is needed from github (this assumes our DB accurately reflects GH's, which should
-            // be true in most cases by the time this is called)
-            let post = status_comment.post(Some(proposal.fk_bot_tracking_comment));
+        // if the comment body in the database equals the new one we generated, then no change
+        // is needed from github (this assumes our DB accurately reflects GH's, which should
+        // be true in most cases by the time this is called)
+        let status_changed = previous_comment.body != status_comment.body;
+        let fcp_starting =
+            num_active_concerns == 0 && hold.is_none() && quorum_reached(&issue, &reviews);
+
+        if status_changed && !fcp_starting {
+            let post = status_comment.post(ctx, Some(proposal.fk_bot_tracking_comment));
             ok_or_continue!(post, why =>
                 error!("Unable to update status comment for proposal {}: {:?}",
                         proposal.id, why));
         }
 
-        let majority_complete = num_outstanding_reviews < num_complete_reviews;
-
-        if num_active_concerns == 0 && majority_complete && num_outstanding_reviews < 3 {
-            // TODO only record the fcp as started if we know that we successfully commented
-            // i.e. either the comment claims to have posted, or we get a comment back to reconcile
-
-            // FCP can start now -- update the database
-            proposal.fcp_start = Some(Utc::now().naive_utc());
-            let update = diesel::update(fcp_proposal.find(proposal.id))
-                .set(&proposal)
-                .execute(conn);
-            ok_or_continue!(update, why =>
+        if fcp_starting {
+            // FCP can start now -- update the database and announce it in one go, so that we
+            // can't end up with a started FCP nobody was told about
+            let start = ctx.now();
+            proposal.fcp_start = Some(start);
+            // an FCP which a concern paused may pick up where it left off
+            let length = proposal
+                .fcp_remaining_secs
+                .take()
+                .map(Duration::seconds)
+                .unwrap_or_else(|| fcp_length(&issue, &reviews));
+            proposal.extended_end = Some(start + length);
+
+            let comment_type = CommentType::FcpAllReviewedNoConcerns {
+                status_comment_id: proposal.fk_bot_tracking_comment,
+                fcp_end: fcp_end(&proposal, &issue).expect("the FCP has started"),
+            };
+            let fcp_start_comment = RfcBotComment::new(&issue, comment_type);
+
+            // the status comment showing the final reviews goes out along with the rest
+            let mut actions = Vec::new();
+            if status_changed {
+                actions.push(Action::EditComment(
+                    proposal.fk_bot_tracking_comment,
+                    &status_comment.body,
+                ));
+            }
+            // TODO only add label if FCP > 1 day
+            actions.extend(vec![
+                Action::AddLabel(Label::FCP),
+                Action::RemoveLabel(Label::PFCP),
+                Action::Comment(&fcp_start_comment.body),
+            ]);
+
+            let start_fcp = conn.transaction::<_, DashError, _>(|| {
+                diesel::update(fcp_proposal.find(proposal.id))
+                    .set(&proposal)
+                    .execute(conn)?;
+                events::record(ctx, &proposal, Event::Started, None, None)?;
+                ctx.enqueue(&issue, &actions)
+            });
+            ok_or_continue!(start_fcp, why =>
                 error!("Unable to mark FCP {} as started: {:?}",
                        proposal.id, why));
-
-            // attempt to add the final-comment-period label
-            // TODO only add label if FCP > 1 day
-            use crate::config::CONFIG;
-            if CONFIG.post_comments {
-                let label_res = issue.add_label(Label::FCP);
-                issue.remove_label(Label::PFCP);
-                let added_label = match label_res {
-                    Ok(()) => true,
-                    Err(why) => {
-                        warn!(
-                            "Unable to add FCP label to {}#{}: {:?}",
-                            &issue.repository, issue.number, why
-                        );
-                        false
-                    }
-                };
-
-                let comment_type = CommentType::FcpAllReviewedNoConcerns {
-                    added_label,
-                    author: &initiator,
-                    status_comment_id: proposal.fk_bot_tracking_comment,
-                };
-
-                // leave a comment for FCP start
-                let fcp_start_comment = RfcBotComment::new(&issue, comment_type);
-                ok_or_continue!(fcp_start_comment.post(None), why =>
-                    error!("Unable to post comment for FCP {}'s start: {:?}",
-                            proposal.id, why));
-            }
         }
     }
 
     Ok(())
 }
 
-fn evaluate_ffcps() -> DashResult<()> {
+fn evaluate_ffcps(ctx: &Context<'_>) -> DashResult<()> {
     use crate::domain::schema::fcp_proposal::dsl::*;
     use diesel::prelude::*;
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
 
-    // look for any FCP proposals that entered FCP a week or more ago but aren't marked as closed
-    let one_business_week_ago = Utc::now().naive_utc() - Duration::days(10);
-    let ffcps = fcp_proposal
-        .filter(fcp_start.le(one_business_week_ago))
-        .filter(fcp_closed.eq(false))
-        .load::<FcpProposal>(conn);
-    let finished_fcps = ok_or!(ffcps, why => {
+    // look for any FCP proposals whose final comment period is over but aren't marked as closed
+    let running = ok_or!(running_fcps(ctx), why => {
         error!("Unable to retrieve FCPs that need to be marked as finished: {:?}",
                why);
         throw!(why);
     });
+    let now = ctx.now();
+    let finished_fcps = running
+        .into_iter()
+        .filter(|(proposal, _)| ctx.in_scope(proposal.fk_issue))
+        .filter(|(proposal, issue)| fcp_end(proposal, issue).map_or(false, |end| end <= now));
 
-    for mut proposal in finished_fcps {
-        let initiator = githubuser::table
-            .find(proposal.fk_initiator)
-            .first::<GitHubUser>(conn);
-        let initiator = ok_or_continue!(initiator, why =>
-            error!("Unable to retrieve proposal initiator for proposal id {}: {:?}",
-                    proposal.id,
-                    why));
+    for (mut proposal, issue) in finished_fcps {
+        // parse the disposition:
+        let disp = FcpDisposition::from_str(&proposal.disposition)?;
 
-        let issue = issue::table.find(proposal.fk_issue).first::<Issue>(conn);
-        let issue = ok_or_continue!(issue, why =>
-            error!("Unable to find issue to match proposal {}: {:?}",
-                   proposal.id, why));
+        // Build the comment:
+        let comment_type = CommentType::FcpWeekPassed {
+            status_comment_id: proposal.fk_bot_tracking_comment,
+            disposition: disp,
+        };
+        let fcp_close_comment = RfcBotComment::new(&issue, comment_type);
 
-        // TODO only update the db if the comment posts, but reconcile if we find out it worked
+        // Add FFCP label and remove FCP label, then announce it.
+        let mut actions = vec![
+            Action::AddLabel(Label::FFCP),
+            Action::RemoveLabel(Label::FCP),
+            Action::RemoveLabel(Label::WaitingOnFCP),
+        ];
+        // not all repos have this label, and adding it to those would fail
+        if ffcp_adds_waiting_on_review(&issue) {
+            actions.push(Action::AddLabel(Label::WaitingOnReview));
+        }
+        actions.extend(vec![
+            Action::AddLabel(Label::ToAnnounce),
+            Action::Comment(&fcp_close_comment.body),
+        ]);
+        actions.extend(ffcp_actions(&issue, disp));
 
-        // update the fcp
+        // update the fcp in the same transaction as recording what to tell GitHub
         proposal.fcp_closed = true;
-        let update_fcp = diesel::update(fcp_proposal.find(proposal.id))
-            .set(&proposal)
-            .execute(conn);
-        ok_or_continue!(update_fcp, why =>
-            error!("Unable to update FCP {}: {:?}", proposal.id, why));
-
-        // parse the disposition:
-        let disp = FcpDisposition::from_str(&proposal.disposition)?;
-
-        // Add FFCP label and remove FCP label.
-        let label_res = issue.add_label(Label::FFCP);
-        issue.remove_label(Label::FCP);
-        issue.remove_label(Label::WaitingOnFCP);
-        let _ = issue.add_label(Label::WaitingOnReview); // fine to ignore errors, not all repos have the label
-        let added_label = match label_res {
-            Ok(_) => {
-                if let Err(why) = issue.add_label(Label::ToAnnounce) {
-                    warn!(
-                        "Unable to add to-announce label to {}#{}: {:?}",
-                        &issue.repository, issue.number, why
-                    );
-                }
-                true
-            }
-            Err(why) => {
-                warn!(
-                    "Unable to add Finished-FCP label to {}#{}: {:?}",
-                    &issue.repository, issue.number, why
-                );
-                false
-            }
-        };
-
-        // Build the comment:
-        let comment_type = CommentType::FcpWeekPassed {
-            added_label,
-            author: &initiator,
-            status_comment_id: proposal.fk_bot_tracking_comment,
-            disposition: disp,
-        };
-        let fcp_close_comment = RfcBotComment::new(&issue, comment_type);
-
-        // Post it!
-        ok_or_continue!(fcp_close_comment.post(None), why =>
-            error!("Unable to post FCP-ending comment for proposal {}: {:?}",
-                    proposal.id, why));
-
-        execute_ffcp_actions(&issue, disp);
+        let finish_fcp = conn.transaction::<_, DashError, _>(|| {
+            diesel::update(fcp_proposal.find(proposal.id))
+                .set(&proposal)
+                .execute(conn)?;
+            events::record(ctx, &proposal, Event::Finished, None, None)?;
+            ctx.enqueue(&issue, &actions)
+        });
+        ok_or_continue!(finish_fcp, why =>
+            error!("Unable to update FCP {}: {:?}", proposal.id, why));
     }
 
     Ok(())
 }
 
-fn can_ffcp_close(issue: &Issue) -> bool {
+fn should_fcp_resume_after_concern(issue: &Issue) -> bool {
+    SETUP
+        .read()
+        .unwrap()
+        .should_fcp_resume_after_concern(&issue.repository)
+}
+
+pub(super) fn can_ffcp_close(issue: &Issue) -> bool {
     SETUP
         .read()
         .unwrap()
         .should_ffcp_auto_close(&issue.repository)
 }
 
-fn can_ffcp_postpone(issue: &Issue) -> bool {
+pub(super) fn can_ffcp_postpone(issue: &Issue) -> bool {
     SETUP
         .read()
         .unwrap()
         .should_ffcp_auto_postpone(&issue.repository)
 }
 
-fn execute_ffcp_actions(issue: &Issue, disposition: FcpDisposition) {
+fn ffcp_adds_waiting_on_review(issue: &Issue) -> bool {
+    SETUP
+        .read()
+        .unwrap()
+        .should_ffcp_add_waiting_on_review(&issue.repository)
+}
+
+/// The actions to take on an issue whose FCP has finished with the given disposition.
+fn ffcp_actions(issue: &Issue, disposition: FcpDisposition) -> Vec<Action<'static>> {
     match disposition {
         FcpDisposition::Merge => {
             // TODO: This one will require a lot of work to
             // auto-merge RFCs and create the tracking issue.
+            vec![]
         }
-        FcpDisposition::Close if can_ffcp_close(issue) => {
-            let _ = issue.add_label(Label::Closed);
-            issue.remove_label(Label::DispositionClose);
-            issue.close();
-        }
-        FcpDisposition::Postpone if can_ffcp_postpone(issue) => {
-            let _ = issue.add_label(Label::Postponed);
-            issue.remove_label(Label::DispositionPostpone);
-            issue.close();
-        }
-        _ => {}
+        FcpDisposition::Close if can_ffcp_close(issue) => vec![
+            Action::AddLabel(Label::Closed),
+            Action::RemoveLabel(Label::DispositionClose),
+            Action::Close,
+        ],
+        FcpDisposition::Postpone if can_ffcp_postpone(issue) => vec![
+            Action::AddLabel(Label::Postponed),
+            Action::RemoveLabel(Label::DispositionPostpone),
+            Action::Close,
+        ],
+        _ => vec![],
     }
 }
 
-fn list_review_requests(proposal_id: i32) -> DashResult<Vec<(GitHubUser, FcpReviewRequest)>> {
-    let conn = &*DB_POOL.get()?;
+fn list_review_requests(
+    ctx: &Context<'_>,
+    proposal_id: i32,
+) -> DashResult<Vec<(GitHubUser, FcpReviewRequest)>> {
+    let conn = ctx.conn;
 
     let reviews = fcp_review_request::table
         .filter(fcp_review_request::fk_proposal.eq(proposal_id))
@@ -627,8 +1155,87 @@ fn list_review_requests(proposal_id: i32) -> DashResult<Vec<(GitHubUser, FcpRevi
     Ok(w_reviewers)
 }
 
-fn list_poll_response_requests(poll_id: i32) -> DashResult<Vec<(GitHubUser, PollResponseRequest)>> {
-    let conn = &*DB_POOL.get()?;
+/// Find the unresolved concern raised by `login` which `name_or_number` refers to, by its name or
+/// by its number in the status comment. If there isn't one, the error says `not_found` and
+/// suggests the closest name.
+fn find_unresolved_concern(
+    ctx: &Context<'_>,
+    proposal: &FcpProposal,
+    login: &str,
+    name_or_number: &str,
+    not_found: String,
+) -> DashResult<(GitHubUser, FcpConcern)> {
+    let concerns = list_concerns_with_authors(ctx, proposal.id)?;
+
+    let key = command::concern_key(name_or_number);
+    let numbered = name_or_number
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|i| concerns.get(i));
+    let found = concerns
+        .iter()
+        .find(|(_, concern)| command::concern_key(&concern.name) == key)
+        .or(numbered);
+
+    let candidate = |(initiator, concern): &&(GitHubUser, FcpConcern)| {
+        initiator.login == login && concern.fk_resolved_comment.is_none()
+    };
+    if let Some(found) = found.filter(candidate) {
+        return Ok(found.clone());
+    }
+
+    let suggestion = command::closest(
+        name_or_number,
+        concerns
+            .iter()
+            .filter(candidate)
+            .map(|(_, concern)| (concern.name.as_str(), concern.name.as_str())),
+    );
+    throw!(DashError::CommentableError(command::did_you_mean(
+        not_found, suggestion
+    )))
+}
+
+/// Load who put a proposal on hold and why, if it is on hold.
+fn load_hold(
+    ctx: &Context<'_>,
+    proposal: &FcpProposal,
+) -> DashResult<Option<(GitHubUser, String)>> {
+    let conn = ctx.conn;
+
+    Ok(match (proposal.fk_held_by, &proposal.hold_reason) {
+        (Some(holder), Some(reason)) => Some((
+            githubuser::table.find(holder).first::<GitHubUser>(conn)?,
+            reason.clone(),
+        )),
+        _ => None,
+    })
+}
+
+/// Load the users who delegated any of these review requests.
+fn list_delegators(
+    ctx: &Context<'_>,
+    reviews: &[(GitHubUser, FcpReviewRequest)],
+) -> DashResult<Vec<GitHubUser>> {
+    use diesel::pg::expression::dsl::any;
+    let conn = ctx.conn;
+
+    let ids = reviews
+        .iter()
+        .filter_map(|(_, review)| review.fk_delegated_from)
+        .collect::<Vec<_>>();
+
+    Ok(githubuser::table
+        .filter(githubuser::id.eq(any(ids)))
+        .load::<GitHubUser>(conn)?)
+}
+
+fn list_poll_response_requests(
+    ctx: &Context<'_>,
+    poll_id: i32,
+) -> DashResult<Vec<(GitHubUser, PollResponseRequest)>> {
+    let conn = ctx.conn;
 
     let reviews = poll_response_request::table
         .filter(poll_response_request::fk_poll.eq(poll_id))
@@ -649,12 +1256,16 @@ fn list_poll_response_requests(poll_id: i32) -> DashResult<Vec<(GitHubUser, Poll
     Ok(w_reviewers)
 }
 
-fn list_concerns_with_authors(proposal_id: i32) -> DashResult<Vec<(GitHubUser, FcpConcern)>> {
-    let conn = &*DB_POOL.get()?;
+fn list_concerns_with_authors(
+    ctx: &Context<'_>,
+    proposal_id: i32,
+) -> DashResult<Vec<(GitHubUser, FcpConcern)>> {
+    let conn = ctx.conn;
 
+    // in the order they were raised, so that a concern's number in the status comment stays the same
     let concerns = fcp_concern::table
         .filter(fcp_concern::fk_proposal.eq(proposal_id))
-        .order(fcp_concern::name)
+        .order(fcp_concern::id)
         .load::<FcpConcern>(conn)?;
 
     let mut w_authors = Vec::with_capacity(concerns.len());
@@ -671,12 +1282,13 @@ fn list_concerns_with_authors(proposal_id: i32) -> DashResult<Vec<(GitHubUser, F
 }
 
 fn resolve_applicable_feedback_requests(
+    ctx: &Context<'_>,
     author: &GitHubUser,
     issue: &Issue,
     comment: &IssueComment,
 ) -> DashResult<()> {
     use crate::domain::schema::rfc_feedback_request::dsl::*;
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
 
     // check for an open feedback request, close since no longer applicable
     let existing_request = rfc_feedback_request
@@ -695,9 +1307,12 @@ fn resolve_applicable_feedback_requests(
     Ok(())
 }
 
-fn resolve_logins_to_users(member_logins: &[String]) -> DashResult<Vec<GitHubUser>> {
+fn resolve_logins_to_users(
+    ctx: &Context<'_>,
+    member_logins: &[String],
+) -> DashResult<Vec<GitHubUser>> {
     use diesel::pg::expression::dsl::any;
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
 
     // resolve each member into an actual user
     let users = githubuser::table
@@ -710,7 +1325,7 @@ fn resolve_logins_to_users(member_logins: &[String]) -> DashResult<Vec<GitHubUse
 
 /// Check if an issue comment is written by a member of one of the subteams
 /// satisfying the given predicate.
-fn specific_subteam_members<F>(included: F) -> DashResult<Vec<GitHubUser>>
+fn specific_subteam_members<F>(ctx: &Context<'_>, included: F) -> DashResult<Vec<GitHubUser>>
 where
     F: Fn(&String) -> bool,
 {
@@ -722,17 +1337,48 @@ where
         .collect::<BTreeSet<_>>()
         .into_iter() // diesel won't work with btreeset, and dedup has weird lifetime errors
         .collect::<Vec<_>>();
-    resolve_logins_to_users(&members)
+    resolve_logins_to_users(ctx, &members)
+}
+
+/// List the members of each of the subteams satisfying the given predicate, paired with the
+/// label of the team they're a member of.
+fn specific_subteam_reviewers<F>(
+    ctx: &Context<'_>,
+    included: F,
+) -> DashResult<Vec<(String, GitHubUser)>>
+where
+    F: Fn(&String) -> bool,
+{
+    let setup = SETUP.read().unwrap();
+    let mut reviewers = Vec::new();
+    for (label, team) in setup.teams().filter(|&(label, _)| included(&label.0)) {
+        let logins = team
+            .member_logins()
+            .map(std::string::ToString::to_string)
+            .collect::<Vec<_>>();
+        for member in resolve_logins_to_users(ctx, &logins)? {
+            reviewers.push((label.0.clone(), member));
+        }
+    }
+    Ok(reviewers)
+}
+
+/// List the members of each of the subteams labelled on the issue, paired with the label of the
+/// team they're a member of.
+fn subteam_reviewers(ctx: &Context<'_>, issue: &Issue) -> DashResult<Vec<(String, GitHubUser)>> {
+    specific_subteam_reviewers(ctx, |label| issue.labels.contains(label))
 }
 
 /// Return a list of all known team members.
-fn all_team_members() -> DashResult<Vec<GitHubUser>> { specific_subteam_members(|_| true) }
+fn all_team_members(ctx: &Context<'_>) -> DashResult<Vec<GitHubUser>> {
+    specific_subteam_members(ctx, |_| true)
+}
 
 /// Check if an issue comment is written by a member of one of the subteams
 /// labelled on the issue.
-fn subteam_members(issue: &Issue) -> DashResult<Vec<GitHubUser>> {
+fn subteam_members(ctx: &Context<'_>, issue: &Issue) -> DashResult<Vec<GitHubUser>> {
     // retrieve all of the teams tagged on this issue
-    specific_subteam_members(|label| issue.labels.contains(&label))
+    specific_subteam_members(ctx, |label| issue.labels.contains(&label))
 }
 
 /// Check if an issue comment is written by a member of one of the subteams
@@ -746,45 +1392,147 @@ fn all_teams_for_issue(issue: &Issue) -> BTreeSet<String> {
         .collect::<BTreeSet<_>>()
 }
 
-fn cancel_fcp(author: &GitHubUser, issue: &Issue, existing: &FcpProposal) -> DashResult<()> {
+/// Cancel a proposal, either because of `comment` or because the issue was closed.
+fn cancel_fcp(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    existing: &FcpProposal,
+    comment: Option<&IssueComment>,
+) -> DashResult<()> {
     use crate::domain::schema::fcp_proposal::dsl::*;
 
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
+
+    // leave github comment stating that FCP proposal cancelled
+    let announcement = RfcBotComment::new(issue, CommentType::FcpProposalCancelled(author));
 
     // if exists delete FCP with associated concerns, reviews, feedback requests
     // db schema has ON DELETE CASCADE
-    diesel::delete(fcp_proposal.filter(id.eq(existing.id))).execute(conn)?;
+    conn.transaction::<_, DashError, _>(|| {
+        diesel::delete(fcp_proposal.filter(id.eq(existing.id))).execute(conn)?;
+        events::record(
+            ctx,
+            existing,
+            Event::Cancelled,
+            comment.map(|c| c.fk_user),
+            comment.map(|c| c.id),
+        )?;
+        ctx.enqueue(
+            issue,
+            &[
+                Action::Comment(&announcement.body),
+                Action::RemoveLabel(Label::FCP),
+                Action::RemoveLabel(Label::PFCP),
+                Action::RemoveLabel(Label::DispositionMerge),
+                Action::RemoveLabel(Label::DispositionClose),
+                Action::RemoveLabel(Label::DispositionPostpone),
+            ],
+        )
+    })
+}
 
-    // leave github comment stating that FCP proposal cancelled
-    let comment = RfcBotComment::new(issue, CommentType::FcpProposalCancelled(author));
-    let _ = comment.post(None);
-    [
-        Label::FCP,
-        Label::PFCP,
-        Label::DispositionMerge,
-        Label::DispositionClose,
-        Label::DispositionPostpone,
-    ]
-    .iter()
-    .for_each(|&lab| issue.remove_label(lab));
+/// If a deleted comment was the status comment of a proposal or a poll, post the status comment
+/// again so that it isn't left pointing at a comment which no longer exists. A finished FCP
+/// doesn't need one anymore, so its status comment is left alone.
+fn repost_status_comments(
+    ctx: &Context<'_>,
+    issue: &Issue,
+    deleted: &IssueComment,
+) -> DashResult<()> {
+    let conn = ctx.conn;
+
+    let proposal = fcp_proposal::table
+        .filter(fcp_proposal::fk_bot_tracking_comment.eq(deleted.id))
+        .filter(fcp_proposal::fcp_closed.eq(false))
+        .first::<FcpProposal>(conn)
+        .optional()?;
+
+    if let Some(mut proposal) = proposal {
+        let initiator = githubuser::table
+            .find(proposal.fk_initiator)
+            .first::<GitHubUser>(conn)?;
+        let reviews = list_review_requests(ctx, proposal.id)?;
+        let delegators = list_delegators(ctx, &reviews)?;
+        let concerns = list_concerns_with_authors(ctx, proposal.id)?;
+        let hold = load_hold(ctx, &proposal)?;
+
+        let status_comment = post_insert_comment(
+            ctx,
+            issue,
+            CommentType::FcpProposed(
+                &initiator,
+                FcpDisposition::from_str(&proposal.disposition)?,
+                &reviews,
+                &concerns,
+                &delegators,
+                hold.as_ref()
+                    .map(|(holder, reason)| (holder, reason.as_str())),
+            ),
+        )?;
+
+        proposal.fk_bot_tracking_comment = status_comment.id;
+        conn.transaction::<_, DashError, _>(|| {
+            diesel::update(fcp_proposal::table.find(proposal.id))
+                .set(&proposal)
+                .execute(conn)?;
+            outbox::redirect_edits(conn, deleted.id, status_comment.id)
+        })?;
+    }
+
+    let survey = poll::table
+        .filter(poll::fk_bot_tracking_comment.eq(deleted.id))
+        .first::<Poll>(conn)
+        .optional()?;
+
+    if let Some(mut survey) = survey {
+        let initiator = githubuser::table
+            .find(survey.fk_initiator)
+            .first::<GitHubUser>(conn)?;
+        let responses = list_poll_response_requests(ctx, survey.id)?;
+
+        let status_comment = post_insert_comment(
+            ctx,
+            issue,
+            CommentType::QuestionAsked {
+                initiator: &initiator,
+                respondents: &responses,
+                question: &survey.poll_question,
+                teams: survey.poll_teams.split(',').collect(),
+            },
+        )?;
+
+        survey.fk_bot_tracking_comment = status_comment.id;
+        diesel::update(poll::table.find(survey.id))
+            .set(&survey)
+            .execute(conn)?;
+    }
 
     Ok(())
 }
 
-fn existing_proposal(issue: &Issue) -> DashResult<Option<FcpProposal>> {
+fn existing_proposal(ctx: &Context<'_>, issue: &Issue) -> DashResult<Option<FcpProposal>> {
     use crate::domain::schema::fcp_proposal::dsl::*;
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
     Ok(fcp_proposal
         .filter(fk_issue.eq(issue.id))
         .first::<FcpProposal>(conn)
         .optional()?)
 }
 
-fn post_insert_comment(issue: &Issue, comment: CommentType<'_>) -> DashResult<IssueComment> {
-    let conn = &*DB_POOL.get()?;
+fn post_insert_comment(
+    ctx: &Context<'_>,
+    issue: &Issue,
+    comment: CommentType<'_>,
+) -> DashResult<IssueComment> {
+    let conn = ctx.conn;
 
     let comment = RfcBotComment::new(issue, comment);
-    let comment = comment.post(None)?;
+    if ctx.is_replaying() {
+        // the comment was posted the first time round
+        return replay::recorded_bot_comment(ctx, issue, &comment.body);
+    }
+    let comment = comment.send(None)?;
     info!("Posted base comment to github, no reviewers listed yet");
 
     // at this point our new comment doesn't yet exist in the database, so
@@ -804,29 +1552,53 @@ fn post_insert_comment(issue: &Issue, comment: CommentType<'_>) -> DashResult<Is
 }
 
 impl<'a> RfcBotCommand<'a> {
-    pub fn process(
+    pub(super) fn process(
         self,
+        ctx: &Context<'_>,
         author: &GitHubUser,
         issue: &Issue,
         comment: &IssueComment,
-        team_members: &[GitHubUser],
+        team_members: &[(String, GitHubUser)],
     ) -> DashResult<()> {
         use self::RfcBotCommand::*;
         match self {
-            StartPoll { teams, question } => process_poll(author, issue, comment, question, teams),
-            FcpPropose(disp) => process_fcp_propose(author, issue, comment, team_members, disp),
-            FcpCancel => process_fcp_cancel(author, issue),
-            Reviewed => process_reviewed(author, issue),
-            NewConcern(concern_name) => process_new_concern(author, issue, comment, concern_name),
+            StartPoll { teams, question } => {
+                process_poll(ctx, author, issue, comment, question, teams)
+            }
+            FcpPropose(disp) => {
+                process_fcp_propose(ctx, author, issue, comment, team_members, disp)
+            }
+            FcpCancel => process_fcp_cancel(ctx, author, issue, comment),
+            Reviewed => process_reviewed(ctx, author, issue, comment),
+            Unreview => process_unreview(ctx, author, issue, comment),
+            Abstain(reason) => process_abstain(ctx, author, issue, comment, reason),
+            Delegate(login) => process_delegate(ctx, author, issue, comment, login),
+            AddTeams(teams) => process_change_teams(ctx, author, issue, comment, &teams, true),
+            RemoveTeams(teams) => process_change_teams(ctx, author, issue, comment, &teams, false),
+            FcpExtend(duration) => process_extend(ctx, author, issue, comment, duration),
+            Hold(reason) => process_hold(ctx, author, issue, comment, reason),
+            Unhold => process_unhold(ctx, author, issue, comment),
+            NewConcern(concern_name) => {
+                process_new_concern(ctx, author, issue, comment, concern_name)
+            }
             ResolveConcern(concern_name) => {
-                process_resolve_concern(author, issue, comment, concern_name)
+                process_resolve_concern(ctx, author, issue, comment, concern_name)
+            }
+            ResolveConcernFor { user, concern } => {
+                process_resolve_concern_for(ctx, author, issue, comment, user, concern)
+            }
+            FeedbackRequest(username) => process_feedback_request(ctx, author, issue, username),
+            Status => process_status(ctx, issue),
+            Help => {
+                RfcBotComment::new(issue, CommentType::Help).post(ctx, None)?;
+                Ok(())
             }
-            FeedbackRequest(username) => process_feedback_request(author, issue, username),
         }
     }
 }
 
 fn process_poll(
+    ctx: &Context<'_>,
     author: &GitHubUser,
     issue: &Issue,
     comment: &IssueComment,
@@ -834,7 +1606,7 @@ fn process_poll(
     teams: BTreeSet<&str>,
 ) -> DashResult<()> {
     use crate::domain::schema::poll::dsl::*;
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
 
     let tmp_teams;
     let teams = if teams.is_empty() {
@@ -848,12 +1620,13 @@ fn process_poll(
     } else {
         teams
     };
-    let members = specific_subteam_members(|l| teams.contains(&**l))?;
+    let members = specific_subteam_members(ctx, |l| teams.contains(&**l))?;
 
     info!("adding a new poll to issue.");
 
     // leave github comment stating that question is asked, ping respondents
     let gh_comment = post_insert_comment(
+        ctx,
         issue,
         CommentType::QuestionAsked {
             initiator: author,
@@ -870,7 +1643,7 @@ fn process_poll(
         fk_initiating_comment: comment.id,
         fk_bot_tracking_comment: gh_comment.id,
         poll_question: question,
-        poll_created_at: Utc::now().naive_utc(),
+        poll_created_at: ctx.now(),
         poll_closed: false,
         poll_teams: &*teams_str,
     };
@@ -898,7 +1671,7 @@ fn process_poll(
 
     // they're in the database, but now we need them paired with githubuser
 
-    let response_requests = list_poll_response_requests(new_poll.id)?;
+    let response_requests = list_poll_response_requests(ctx, new_poll.id)?;
 
     debug!("poll response requests inserted into the database");
 
@@ -913,7 +1686,13 @@ fn process_poll(
             respondents: &*response_requests,
         },
     );
-    new_gh_comment.post(Some(gh_comment.id))?;
+    // if this fails, the status comment is brought up to date when the poll is next evaluated
+    if let Err(why) = new_gh_comment.post(ctx, Some(gh_comment.id)) {
+        warn!(
+            "Unable to list respondents in comment {}: {:?}",
+            gh_comment.id, why
+        );
+    }
 
     debug!("github comment updated with poll respondents");
 
@@ -921,197 +1700,1040 @@ fn process_poll(
 }
 
 fn process_fcp_propose(
+    ctx: &Context<'_>,
     author: &GitHubUser,
     issue: &Issue,
     comment: &IssueComment,
-    team_members: &[GitHubUser],
+    team_members: &[(String, GitHubUser)],
     disp: FcpDispositionData<'_>,
 ) -> DashResult<()> {
     debug!("processing fcp proposal: {:?}", disp);
     use crate::domain::schema::fcp_proposal::dsl::*;
 
-    if existing_proposal(issue)?.is_none() {
-        let conn = &*DB_POOL.get()?;
-        // if not exists, create new FCP proposal
-        info!("proposal is a new FCP, creating...");
+    if let Some(existing) = existing_proposal(ctx, issue)? {
+        // proposing something else changes the existing proposal
+        return change_disposition(ctx, author, issue, comment, existing, disp.disp());
+    }
 
-        // leave github comment stating that FCP is proposed, ping reviewers
-        let gh_comment = post_insert_comment(
-            issue,
-            CommentType::FcpProposed(author, disp.disp(), &[], &[]),
-        )?;
+    let conn = ctx.conn;
+    // if not exists, create new FCP proposal
+    info!("proposal is a new FCP, creating...");
 
-        let proposal = NewFcpProposal {
-            fk_issue: issue.id,
-            fk_initiator: author.id,
-            fk_initiating_comment: comment.id,
-            fk_bot_tracking_comment: gh_comment.id,
-            disposition: disp.disp().repr(),
-            fcp_start: None,
-            fcp_closed: false,
-        };
-        let proposal = diesel::insert_into(fcp_proposal)
-            .values(&proposal)
-            .get_result::<FcpProposal>(conn)?;
+    // leave github comment stating that FCP is proposed, ping reviewers
+    let gh_comment = post_insert_comment(
+        ctx,
+        issue,
+        CommentType::FcpProposed(author, disp.disp(), &[], &[], &[], None),
+    )?;
 
-        debug!("proposal inserted into the database");
+    let proposal = NewFcpProposal {
+        id: ctx.proposal_id(comment.id),
+        fk_issue: issue.id,
+        fk_initiator: author.id,
+        fk_initiating_comment: comment.id,
+        fk_bot_tracking_comment: gh_comment.id,
+        disposition: disp.disp().repr(),
+        fcp_start: None,
+        fcp_closed: false,
+    };
+    let proposal = diesel::insert_into(fcp_proposal)
+        .values(&proposal)
+        .get_result::<FcpProposal>(conn)?;
+    events::record(
+        ctx,
+        &proposal,
+        Event::Proposed(disp.disp()),
+        Some(author.id),
+        Some(comment.id),
+    )?;
 
-        // generate review requests for all relevant subteam members
+    debug!("proposal inserted into the database");
 
-        let review_requests = team_members
-            .iter()
-            .map(|member| NewFcpReviewRequest {
-                fk_proposal: proposal.id,
-                fk_reviewer: member.id,
-                // let's assume the initiator has reviewed it
-                reviewed: member.id == author.id,
-            })
-            .collect::<Vec<_>>();
+    // generate review requests for all relevant subteam members
 
-        diesel::insert_into(fcp_review_request::table)
-            .values(&review_requests)
-            .execute(conn)?;
+    let review_requests = team_members
+        .iter()
+        .map(|(team, member)| NewFcpReviewRequest {
+            fk_proposal: proposal.id,
+            fk_reviewer: member.id,
+            // let's assume the initiator has reviewed it
+            reviewed: member.id == author.id,
+            team_label: Some(team),
+        })
+        .collect::<Vec<_>>();
+
+    diesel::insert_into(fcp_review_request::table)
+        .values(&review_requests)
+        .execute(conn)?;
 
-        // they're in the database, but now we need them paired with githubuser
+    // they're in the database, but now we need them paired with githubuser
 
-        let review_requests = list_review_requests(proposal.id)?;
+    let review_requests = list_review_requests(ctx, proposal.id)?;
 
-        debug!("review requests inserted into the database");
+    debug!("review requests inserted into the database");
 
-        // we have all of the review requests, generate a new comment and post it
+    // we have all of the review requests, generate a new comment and post it
 
-        let new_gh_comment = RfcBotComment::new(
-            issue,
-            CommentType::FcpProposed(author, disp.disp(), &review_requests, &[]),
+    let new_gh_comment = RfcBotComment::new(
+        issue,
+        CommentType::FcpProposed(author, disp.disp(), &review_requests, &[], &[], None),
+    );
+    // if this fails, the status comment is brought up to date when the proposal is next
+    // evaluated
+    if let Err(why) = new_gh_comment.post(ctx, Some(gh_comment.id)) {
+        warn!(
+            "Unable to list reviewers in comment {}: {:?}",
+            gh_comment.id, why
         );
-        new_gh_comment.post(Some(gh_comment.id))?;
-        debug!("github comment updated with reviewers");
     }
+    debug!("github comment updated with reviewers");
 
     Ok(())
 }
 
-fn process_fcp_cancel(author: &GitHubUser, issue: &Issue) -> DashResult<()> {
-    if let Some(existing) = existing_proposal(issue)? {
-        cancel_fcp(author, issue, &existing)?;
+/// Change what a pending proposal proposes to do. The reviews given so far were for the old
+/// disposition, so they're reset.
+fn change_disposition(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+    mut proposal: FcpProposal,
+    disposition: FcpDisposition,
+) -> DashResult<()> {
+    let conn = ctx.conn;
+    conn.transaction::<_, DashError, _>(|| {
+        let previous = match update_disposition(ctx, author, comment, &mut proposal, disposition)? {
+            Some(previous) => previous,
+            None => return Ok(()),
+        };
+
+        let announcement = RfcBotComment::new(
+            issue,
+            CommentType::FcpDispositionChanged {
+                author,
+                previous,
+                disposition,
+                status_comment_id: proposal.fk_bot_tracking_comment,
+            },
+        );
+        ctx.enqueue(
+            issue,
+            &[
+                Action::RemoveLabel(previous.label()),
+                Action::AddLabel(disposition.label()),
+                Action::Comment(&announcement.body),
+            ],
+        )
+    })
+}
+
+/// Store a change to what a proposal proposes, if `author` may make it, and reset the reviews.
+/// Returns the previous disposition, or `None` if it's unchanged.
+fn update_disposition(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    comment: &IssueComment,
+    proposal: &mut FcpProposal,
+    disposition: FcpDisposition,
+) -> DashResult<Option<FcpDisposition>> {
+    use crate::domain::schema::fcp_review_request::dsl::*;
+
+    let previous = FcpDisposition::from_str(&proposal.disposition)?;
+    if previous == disposition {
+        return Ok(None);
+    }
+
+    if proposal.fcp_closed {
+        throw!(DashError::CommentableError(format!(
+            "The final comment period to {} this has already finished, so it can't be changed.",
+            previous.repr()
+        )));
+    }
+
+    if proposal.fcp_start.is_some() {
+        throw!(DashError::CommentableError(format!(
+            "The final comment period to {} this has already started, so it can't be changed. \
+             Cancel it with `{} fcp cancel` to propose something else.",
+            previous.repr(),
+            RFC_BOT_MENTIONS[0]
+        )));
+    }
+
+    let conn = ctx.conn;
+
+    let reviewing = fcp_review_request
+        .filter(fk_proposal.eq(proposal.id))
+        .filter(fk_reviewer.eq(author.id))
+        .count()
+        .get_result::<i64>(conn)?;
+    if reviewing == 0 && proposal.fk_initiator != author.id {
+        throw!(DashError::CommentableError(format!(
+            "Only the reviewers of the proposal to {} this can change what it proposes.",
+            previous.repr()
+        )));
+    }
+
+    proposal.disposition = disposition.repr().to_string();
+    conn.transaction::<_, DashError, _>(|| {
+        diesel::update(fcp_proposal::table.find(proposal.id))
+            .set(&*proposal)
+            .execute(conn)?;
+        diesel::update(
+            fcp_review_request
+                .filter(fk_proposal.eq(proposal.id))
+                .filter(fk_reviewer.ne(author.id)),
+        )
+        .set(reviewed.eq(false))
+        .execute(conn)?;
+        events::record(
+            ctx,
+            proposal,
+            Event::DispositionChanged(disposition),
+            Some(author.id),
+            Some(comment.id),
+        )?;
+        // like a new proposal, whoever proposed it counts as having reviewed it
+        review(ctx, proposal, author, comment.id)
+    })?;
+
+    Ok(Some(previous))
+}
+
+fn process_fcp_cancel(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+) -> DashResult<()> {
+    if let Some(existing) = existing_proposal(ctx, issue)? {
+        cancel_fcp(ctx, author, issue, &existing, Some(comment))?;
     }
     Ok(())
 }
 
-fn process_reviewed(author: &GitHubUser, issue: &Issue) -> DashResult<()> {
+fn process_reviewed(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+) -> DashResult<()> {
     // set a reviewed entry for the comment author on this issue
-    if let Some(proposal) = existing_proposal(issue)? {
-        use crate::domain::schema::fcp_review_request::dsl::*;
-        let conn = &*DB_POOL.get()?;
+    if let Some(proposal) = existing_proposal(ctx, issue)? {
+        review(ctx, &proposal, author, comment.id)?;
+    }
+
+    Ok(())
+}
+
+/// Mark a reviewer's review requests for a proposal as reviewed, instead of abstained if they had
+/// abstained.
+fn review(
+    ctx: &Context<'_>,
+    proposal: &FcpProposal,
+    reviewer: &GitHubUser,
+    comment_id: i64,
+) -> DashResult<()> {
+    use crate::domain::schema::fcp_review_request::dsl::*;
+    let conn = ctx.conn;
 
-        let review_request = fcp_review_request
+    // members of several teams have one review request per team
+    let updated = diesel::update(
+        fcp_review_request
             .filter(fk_proposal.eq(proposal.id))
-            .filter(fk_reviewer.eq(author.id))
-            .first::<FcpReviewRequest>(conn)
-            .optional()?;
-
-        if let Some(mut review_request) = review_request {
-            // store an FK to the comment marking for review (not null fk here means
-            // reviewed)
-            review_request.reviewed = true;
-            diesel::update(fcp_review_request.find(review_request.id))
-                .set(&review_request)
-                .execute(conn)?;
+            .filter(fk_reviewer.eq(reviewer.id))
+            .filter(reviewed.eq(false)),
+    )
+    .set((
+        reviewed.eq(true),
+        abstained.eq(false),
+        abstain_reason.eq(None::<String>),
+    ))
+    .execute(conn)?;
+
+    if updated > 0 {
+        events::record(
+            ctx,
+            proposal,
+            Event::Reviewed,
+            Some(reviewer.id),
+            Some(comment_id),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn process_unreview(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+) -> DashResult<()> {
+    if let Some(proposal) = existing_proposal(ctx, issue)? {
+        if proposal.fcp_start.is_some() {
+            throw!(DashError::CommentableError(
+                "The final comment period has already started, so reviews can no longer be \
+                 withdrawn."
+                    .to_string()
+            ));
         }
+
+        withdraw_review(ctx, &proposal, author, comment.id)?;
     }
 
     Ok(())
 }
 
-fn process_new_concern(
+fn process_abstain(
+    ctx: &Context<'_>,
     author: &GitHubUser,
     issue: &Issue,
     comment: &IssueComment,
-    concern_name: &str,
+    reason: Option<&str>,
 ) -> DashResult<()> {
-    if let Some(mut proposal) = existing_proposal(issue)? {
-        // check for existing concern
-        use crate::domain::schema::fcp_concern::dsl::*;
-        use crate::domain::schema::fcp_proposal::dsl::*;
-        let conn = &*DB_POOL.get()?;
+    if let Some(proposal) = existing_proposal(ctx, issue)? {
+        use crate::domain::schema::fcp_review_request::dsl::*;
 
-        let existing_concern = fcp_concern
-            .filter(fk_proposal.eq(proposal.id))
-            .filter(name.eq(concern_name))
-            .first::<FcpConcern>(conn)
-            .optional()?;
+        if proposal.fcp_start.is_some() {
+            throw!(DashError::CommentableError(
+                "The final comment period has already started, so it's too late to abstain."
+                    .to_string()
+            ));
+        }
 
-        if existing_concern.is_none() {
-            // if not exists, create new concern with this author as creator
-            let new_concern = NewFcpConcern {
-                fk_proposal: proposal.id,
-                fk_initiator: author.id,
-                fk_resolved_comment: None,
-                name: concern_name,
-                fk_initiating_comment: comment.id,
-            };
-            diesel::insert_into(fcp_concern)
-                .values(&new_concern)
-                .execute(conn)?;
+        let conn = ctx.conn;
 
-            // Take us out of FCP and back into PFCP if need be:
-            if proposal.fcp_start.is_some() {
-                // Update DB: FCP is not started anymore.
-                proposal.fcp_start = None;
-                let update = diesel::update(fcp_proposal.find(proposal.id))
-                    .set(&proposal)
-                    .execute(conn);
-                ok_or!(update, why => {
-                    error!("Unable to mark FCP {} as unstarted: {:?}", proposal.id, why);
-                    return Ok(());
-                });
+        // members of several teams have one review request per team
+        let updated = diesel::update(
+            fcp_review_request
+                .filter(fk_proposal.eq(proposal.id))
+                .filter(fk_reviewer.eq(author.id)),
+        )
+        .set((
+            reviewed.eq(false),
+            abstained.eq(true),
+            abstain_reason.eq(reason),
+        ))
+        .execute(conn)?;
 
-                // Update labels:
-                let _ = issue.add_label(Label::PFCP);
-                issue.remove_label(Label::FCP);
-            }
+        if updated == 0 {
+            throw!(DashError::CommentableError(
+                "You aren't reviewing this proposal.".to_string()
+            ));
         }
+
+        events::record(
+            ctx,
+            &proposal,
+            Event::Abstained(reason),
+            Some(author.id),
+            Some(comment.id),
+        )?;
     }
 
     Ok(())
 }
 
-fn process_resolve_concern(
+fn process_change_teams(
+    ctx: &Context<'_>,
     author: &GitHubUser,
     issue: &Issue,
     comment: &IssueComment,
-    concern_name: &str,
+    teams: &BTreeSet<&str>,
+    add: bool,
 ) -> DashResult<()> {
-    debug!("Command is to resolve a concern ({}).", concern_name);
+    if let Some(proposal) = existing_proposal(ctx, issue)? {
+        if proposal.fcp_start.is_some() {
+            throw!(DashError::CommentableError(
+                "The final comment period has already started, so the teams reviewing it can no \
+                 longer be changed."
+                    .to_string()
+            ));
+        }
+
+        let conn = ctx.conn;
+        conn.transaction::<_, DashError, _>(|| {
+            if add {
+                add_review_teams(ctx, &proposal, teams, Some(author.id), Some(comment.id))
+            } else {
+                remove_review_teams(ctx, &proposal, teams, Some(author.id), Some(comment.id))
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+fn process_extend(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+    duration: Duration,
+) -> DashResult<()> {
+    use crate::domain::schema::fcp_proposal::dsl::*;
+    let conn = ctx.conn;
+
+    let mut proposal = match existing_proposal(ctx, issue)? {
+        Some(proposal) if proposal.fcp_start.is_some() && !proposal.fcp_closed => proposal,
+        _ => throw!(DashError::CommentableError(
+            "There's no final comment period running to extend.".to_string()
+        )),
+    };
+
+    let end = fcp_end(&proposal, issue).expect("the FCP has started") + duration;
+    if end <= ctx.now() {
+        throw!(DashError::CommentableError(
+            "That would end the final comment period before now.".to_string()
+        ));
+    }
+
+    let announcement = RfcBotComment::new(
+        issue,
+        CommentType::FcpExtended {
+            author,
+            shortened: duration < Duration::zero(),
+            fcp_end: end,
+        },
+    );
+
+    proposal.extended_end = Some(end);
+    conn.transaction::<_, DashError, _>(|| {
+        diesel::update(fcp_proposal.find(proposal.id))
+            .set(extended_end.eq(proposal.extended_end))
+            .execute(conn)?;
+        events::record(
+            ctx,
+            &proposal,
+            Event::Extended,
+            Some(author.id),
+            Some(comment.id),
+        )?;
+        ctx.enqueue(issue, &[Action::Comment(&announcement.body)])
+    })
+}
+
+fn process_hold(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+    reason: &str,
+) -> DashResult<()> {
+    use crate::domain::schema::fcp_proposal::dsl::*;
+    let conn = ctx.conn;
+
+    let mut proposal = match existing_proposal(ctx, issue)? {
+        Some(proposal) if !proposal.fcp_closed => proposal,
+        _ => throw!(DashError::CommentableError(
+            "There's no proposal to put on hold.".to_string()
+        )),
+    };
+
+    // holding it again only changes the reason, the clock stays stopped from the first time
+    proposal.held_at = proposal.held_at.or_else(|| Some(ctx.now()));
+    proposal.hold_reason = Some(reason.to_string());
+    proposal.fk_held_by = Some(author.id);
+    conn.transaction::<_, DashError, _>(|| {
+        diesel::update(fcp_proposal.find(proposal.id))
+            .set(&proposal)
+            .execute(conn)?;
+        events::record(
+            ctx,
+            &proposal,
+            Event::Held(reason),
+            Some(author.id),
+            Some(comment.id),
+        )?;
+
+        // the status comment of a pending proposal shows the hold, but once the FCP has started
+        // nothing else would
+        if proposal.fcp_start.is_some() {
+            let announcement = RfcBotComment::new(issue, CommentType::FcpHeld { author, reason });
+            ctx.enqueue(issue, &[Action::Comment(&announcement.body)])?;
+        }
+
+        Ok(())
+    })
+}
+
+fn process_unhold(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+) -> DashResult<()> {
+    use crate::domain::schema::fcp_proposal::dsl::*;
+    let conn = ctx.conn;
+
+    let (mut proposal, since) = match existing_proposal(ctx, issue)? {
+        Some(proposal) => match proposal.held_at {
+            Some(since) => (proposal, since),
+            None => throw!(DashError::CommentableError(
+                "The proposal isn't on hold.".to_string()
+            )),
+        },
+        None => throw!(DashError::CommentableError(
+            "The proposal isn't on hold.".to_string()
+        )),
+    };
+
+    // the FCP gets back the time it spent on hold
+    let end = fcp_end(&proposal, issue).map(|end| end + (ctx.now() - since));
+    proposal.extended_end = end.or(proposal.extended_end);
+    proposal.held_at = None;
+    proposal.hold_reason = None;
+    proposal.fk_held_by = None;
+    conn.transaction::<_, DashError, _>(|| {
+        diesel::update(fcp_proposal.find(proposal.id))
+            .set(&proposal)
+            .execute(conn)?;
+        events::record(
+            ctx,
+            &proposal,
+            Event::Unheld,
+            Some(author.id),
+            Some(comment.id),
+        )?;
+
+        if let Some(end) = end {
+            let announcement = RfcBotComment::new(
+                issue,
+                CommentType::FcpUnheld {
+                    author,
+                    fcp_end: end,
+                },
+            );
+            ctx.enqueue(issue, &[Action::Comment(&announcement.body)])?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Ask the members of some more teams to review a proposal. Teams which are already reviewing it
+/// are left alone.
+fn add_review_teams(
+    ctx: &Context<'_>,
+    proposal: &FcpProposal,
+    teams: &BTreeSet<&str>,
+    actor: Option<i32>,
+    comment_id: Option<i64>,
+) -> DashResult<()> {
+    use crate::domain::schema::fcp_review_request::dsl::*;
+    let conn = ctx.conn;
+
+    let reviewing = fcp_review_request
+        .filter(fk_proposal.eq(proposal.id))
+        .select(team_label)
+        .load::<Option<String>>(conn)?;
+
+    for &team in teams {
+        if reviewing.iter().any(|label| label.as_deref() == Some(team)) {
+            continue;
+        }
+
+        let members = specific_subteam_reviewers(ctx, |label| label == team)?;
+        let review_requests = members
+            .iter()
+            .map(|(_, member)| NewFcpReviewRequest {
+                fk_proposal: proposal.id,
+                fk_reviewer: member.id,
+                reviewed: false,
+                team_label: Some(team),
+            })
+            .collect::<Vec<_>>();
+        diesel::insert_into(fcp_review_request)
+            .values(&review_requests)
+            .execute(conn)?;
+
+        events::record(ctx, proposal, Event::TeamAdded(team), actor, comment_id)?;
+    }
+
+    Ok(())
+}
+
+/// Stop asking some teams to review a proposal, as long as someone is left to review it.
+fn remove_review_teams(
+    ctx: &Context<'_>,
+    proposal: &FcpProposal,
+    teams: &BTreeSet<&str>,
+    actor: Option<i32>,
+    comment_id: Option<i64>,
+) -> DashResult<()> {
+    use crate::domain::schema::fcp_review_request::dsl::*;
+    let conn = ctx.conn;
+
+    let reviewing = fcp_review_request
+        .filter(fk_proposal.eq(proposal.id))
+        .select(team_label)
+        .load::<Option<String>>(conn)?;
+
+    let remaining = reviewing
+        .iter()
+        .filter(|label| {
+            !label
+                .as_deref()
+                .map_or(false, |label| teams.contains(label))
+        })
+        .count();
+    if remaining == 0 {
+        throw!(DashError::CommentableError(format!(
+            "Can't stop every team from reviewing the proposal. Cancel it with `{} fcp cancel` \
+             instead.",
+            RFC_BOT_MENTIONS[0]
+        )));
+    }
+
+    for &team in teams {
+        let removed = diesel::delete(
+            fcp_review_request
+                .filter(fk_proposal.eq(proposal.id))
+                .filter(team_label.eq(team)),
+        )
+        .execute(conn)?;
+
+        if removed > 0 {
+            events::record(ctx, proposal, Event::TeamRemoved(team), actor, comment_id)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn process_delegate(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+    login: &str,
+) -> DashResult<()> {
+    use crate::domain::schema::fcp_review_request::dsl::*;
+
+    let proposal = match existing_proposal(ctx, issue)? {
+        Some(proposal) => proposal,
+        None => return Ok(()),
+    };
+    if proposal.fcp_start.is_some() {
+        throw!(DashError::CommentableError(
+            "The final comment period has already started, so reviews can no longer be \
+             delegated."
+                .to_string()
+        ));
+    }
+
+    let conn = ctx.conn;
+
+    let delegate = githubuser::table
+        .filter(githubuser::login.eq(login))
+        .first::<GitHubUser>(conn)
+        .optional()?;
+    let delegate = match delegate {
+        Some(delegate) if delegate.id != author.id => delegate,
+        _ => throw!(DashError::CommentableError(format!(
+            "Can't delegate your review to @{}.",
+            login
+        ))),
+    };
+
+    let pending = fcp_review_request
+        .filter(fk_proposal.eq(proposal.id))
+        .filter(fk_reviewer.eq(author.id))
+        .filter(reviewed.eq(false))
+        .filter(abstained.eq(false))
+        .load::<FcpReviewRequest>(conn)?;
+    if pending.is_empty() {
+        throw!(DashError::CommentableError(
+            "You don't have a pending review to delegate.".to_string()
+        ));
+    }
 
-    if let Some(proposal) = existing_proposal(issue)? {
+    let already_reviewing = fcp_review_request
+        .filter(fk_proposal.eq(proposal.id))
+        .filter(fk_reviewer.eq(delegate.id))
+        .select(team_label)
+        .load::<Option<String>>(conn)?;
+
+    // each review is handed over if the delegate is on the team it was requested for
+    let mut delegated = Vec::new();
+    for request in pending {
+        if already_reviewing.contains(&request.team_label) {
+            continue;
+        }
+        let members = match &request.team_label {
+            Some(team) => specific_subteam_reviewers(ctx, |label| label == team)?,
+            None => subteam_reviewers(ctx, issue)?,
+        };
+        if members.iter().any(|(_, member)| member.id == delegate.id) {
+            delegated.push(request);
+        }
+    }
+    if delegated.is_empty() {
+        throw!(DashError::CommentableError(format!(
+            "@{} isn't on the team you're reviewing for, or is already reviewing this.",
+            delegate.login
+        )));
+    }
+
+    conn.transaction::<_, DashError, _>(|| {
+        for request in &delegated {
+            // delegating back to whoever delegated the review undoes the delegation
+            let delegator = request.fk_delegated_from.unwrap_or(author.id);
+            let delegated_from = if delegator == delegate.id {
+                None
+            } else {
+                Some(delegator)
+            };
+            diesel::update(fcp_review_request.find(request.id))
+                .set((
+                    fk_reviewer.eq(delegate.id),
+                    fk_delegated_from.eq(delegated_from),
+                ))
+                .execute(conn)?;
+        }
+        events::record(
+            ctx,
+            &proposal,
+            Event::Delegated(&delegate.login),
+            Some(author.id),
+            Some(comment.id),
+        )
+    })
+}
+
+/// Mark a reviewer's review requests for a proposal as neither reviewed nor abstained after all.
+fn withdraw_review(
+    ctx: &Context<'_>,
+    proposal: &FcpProposal,
+    reviewer: &GitHubUser,
+    comment_id: i64,
+) -> DashResult<()> {
+    use crate::domain::schema::fcp_review_request::dsl::*;
+    let conn = ctx.conn;
+
+    // members of several teams have one review request per team
+    let updated = diesel::update(
+        fcp_review_request
+            .filter(fk_proposal.eq(proposal.id))
+            .filter(fk_reviewer.eq(reviewer.id))
+            .filter(reviewed.eq(true).or(abstained.eq(true))),
+    )
+    .set((
+        reviewed.eq(false),
+        abstained.eq(false),
+        abstain_reason.eq(None::<String>),
+    ))
+    .execute(conn)?;
+
+    if updated > 0 {
+        events::record(
+            ctx,
+            proposal,
+            Event::Unreviewed,
+            Some(reviewer.id),
+            Some(comment_id),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn process_new_concern(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+    concern_name: &str,
+) -> DashResult<()> {
+    if let Some(mut proposal) = existing_proposal(ctx, issue)? {
         // check for existing concern
         use crate::domain::schema::fcp_concern::dsl::*;
-        let conn = &*DB_POOL.get()?;
+        use crate::domain::schema::fcp_proposal::dsl::*;
+        let conn = ctx.conn;
 
+        let key = command::concern_key(concern_name);
         let existing_concern = fcp_concern
             .filter(fk_proposal.eq(proposal.id))
-            .filter(fk_initiator.eq(author.id))
-            .filter(name.eq(concern_name))
-            .first::<FcpConcern>(conn)
-            .optional()?;
-
-        if let Some(mut concern) = existing_concern {
-            // mark concern as resolved by adding resolved_comment
-            debug!("Found a matching concern ({})", concern_name);
-            concern.fk_resolved_comment = Some(comment.id);
+            .select(name)
+            .load::<String>(conn)?
+            .into_iter()
+            .find(|existing| command::concern_key(existing) == key);
+
+        if existing_concern.is_some() {
+            return Ok(());
+        }
+
+        conn.transaction::<_, DashError, _>(|| {
+            // if not exists, create new concern with this author as creator
+            let new_concern = NewFcpConcern {
+                fk_proposal: proposal.id,
+                fk_initiator: author.id,
+                fk_resolved_comment: None,
+                name: concern_name,
+                fk_initiating_comment: comment.id,
+            };
+            diesel::insert_into(fcp_concern)
+                .values(&new_concern)
+                .execute(conn)?;
+            events::record(
+                ctx,
+                &proposal,
+                Event::ConcernRaised(concern_name),
+                Some(author.id),
+                Some(comment.id),
+            )?;
+
+            // Take us out of FCP and back into PFCP if need be, unless it's already over:
+            let end = if proposal.fcp_closed {
+                None
+            } else {
+                fcp_end(&proposal, issue)
+            };
+            if let Some(end) = end {
+                // the clock of an FCP on hold stopped when it was put on hold
+                let paused_at = proposal.held_at.unwrap_or_else(|| ctx.now());
+                let remaining = Some(end - paused_at)
+                    .filter(|_| should_fcp_resume_after_concern(issue))
+                    .map(|remaining| remaining.max(Duration::zero()));
+
+                // Update DB: FCP is not started anymore.
+                proposal.fcp_start = None;
+                proposal.extended_end = None;
+                proposal.fcp_remaining_secs = remaining.map(|remaining| remaining.num_seconds());
+                diesel::update(fcp_proposal.find(proposal.id))
+                    .set(&proposal)
+                    .execute(conn)?;
+                events::record(
+                    ctx,
+                    &proposal,
+                    Event::Paused,
+                    Some(author.id),
+                    Some(comment.id),
+                )?;
+
+                let announcement = RfcBotComment::new(
+                    issue,
+                    CommentType::FcpPaused {
+                        concern_name,
+                        concern_comment_id: comment.id,
+                        remaining,
+                    },
+                );
+                ctx.enqueue(
+                    issue,
+                    &[
+                        Action::AddLabel(Label::PFCP),
+                        Action::RemoveLabel(Label::FCP),
+                        Action::Comment(&announcement.body),
+                    ],
+                )?;
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(())
+}
+
+fn process_resolve_concern(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+    concern_name: &str,
+) -> DashResult<()> {
+    debug!("Command is to resolve a concern ({}).", concern_name);
+
+    if let Some(proposal) = existing_proposal(ctx, issue)? {
+        // check for existing concern
+        use crate::domain::schema::fcp_concern::dsl::*;
+        let conn = ctx.conn;
+
+        let (_, mut concern) = find_unresolved_concern(
+            ctx,
+            &proposal,
+            &author.login,
+            concern_name,
+            format!(
+                "You haven't raised an unresolved concern called `{}`.",
+                concern_name
+            ),
+        )?;
+
+        // mark concern as resolved by adding resolved_comment
+        debug!("Found a matching concern ({})", concern.name);
+        concern.fk_resolved_comment = Some(comment.id);
+        diesel::update(fcp_concern.find(concern.id))
+            .set(&concern)
+            .execute(conn)?;
+        events::record(
+            ctx,
+            &proposal,
+            Event::ConcernResolved(&concern.name),
+            Some(author.id),
+            Some(comment.id),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Withdraw the concerns raised by a comment which satisfy the given predicate.
+fn withdraw_concerns<F>(
+    ctx: &Context<'_>,
+    issue: &Issue,
+    comment: &IssueComment,
+    withdrawn: F,
+) -> DashResult<()>
+where
+    F: Fn(&FcpConcern) -> bool,
+{
+    if let Some(proposal) = existing_proposal(ctx, issue)? {
+        use crate::domain::schema::fcp_concern::dsl::*;
+        let conn = ctx.conn;
+
+        let concerns = fcp_concern
+            .filter(fk_proposal.eq(proposal.id))
+            .filter(fk_initiating_comment.eq(comment.id))
+            .load::<FcpConcern>(conn)?;
+
+        for concern in concerns.iter().filter(|c| withdrawn(c)) {
+            conn.transaction::<_, DashError, _>(|| {
+                diesel::delete(fcp_concern.find(concern.id)).execute(conn)?;
+                events::record(
+                    ctx,
+                    &proposal,
+                    Event::ConcernWithdrawn(&concern.name),
+                    Some(comment.fk_user),
+                    Some(comment.id),
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+fn process_status(ctx: &Context<'_>, issue: &Issue) -> DashResult<()> {
+    let conn = ctx.conn;
+
+    let proposal = existing_proposal(ctx, issue)?;
+    let (reviewers, concerns) = match &proposal {
+        Some(proposal) => (
+            list_review_requests(ctx, proposal.id)?,
+            list_concerns_with_authors(ctx, proposal.id)?,
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    let mut polls = Vec::new();
+    for survey in poll::table
+        .filter(poll::fk_issue.eq(issue.id))
+        .filter(poll::poll_closed.eq(false))
+        .order(poll::poll_created_at)
+        .load::<Poll>(conn)?
+    {
+        let respondents = list_poll_response_requests(ctx, survey.id)?;
+        polls.push((survey, respondents));
+    }
+
+    let status = RfcBotComment::new(
+        issue,
+        CommentType::Status {
+            proposal: proposal.as_ref(),
+            reviewers: &reviewers,
+            concerns: &concerns,
+            polls: &polls,
+        },
+    );
+    status.post(ctx, None)?;
+
+    Ok(())
+}
+
+fn process_resolve_concern_for(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    comment: &IssueComment,
+    login: &str,
+    concern_name: &str,
+) -> DashResult<()> {
+    debug!(
+        "Command is to resolve a concern ({}) for {}.",
+        concern_name, login
+    );
+
+    if let Some(proposal) = existing_proposal(ctx, issue)? {
+        use crate::domain::schema::fcp_concern::dsl::*;
+        let conn = ctx.conn;
+
+        let (initiator, mut concern) = find_unresolved_concern(
+            ctx,
+            &proposal,
+            login,
+            concern_name,
+            format!(
+                "`{}` has no unresolved concern called `{}`.",
+                login, concern_name
+            ),
+        )?;
+
+        let departed = !all_team_members(ctx)?.contains(&initiator);
+        let (lead, policy) = {
+            let setup = SETUP.read().unwrap();
+            let lead = all_teams_for_issue(issue)
+                .iter()
+                .any(|team| setup.is_team_lead(team, &author.login));
+            (lead, setup.resolve_for_policy())
+        };
+        let allowed = lead
+            || match policy {
+                ResolveForPolicy::Leads => false,
+                ResolveForPolicy::DepartedMembers => departed,
+                ResolveForPolicy::AnyMember => true,
+            };
+        if !allowed {
+            throw!(DashError::CommentableError(
+                match policy {
+                    ResolveForPolicy::DepartedMembers => {
+                        "Only team leads can resolve concerns raised by current team members on \
+                         their behalf."
+                    }
+                    _ => "Only team leads can resolve concerns on behalf of whoever raised them.",
+                }
+                .to_string()
+            ));
+        }
+
+        concern.fk_resolved_comment = Some(comment.id);
+        conn.transaction::<_, DashError, _>(|| {
             diesel::update(fcp_concern.find(concern.id))
                 .set(&concern)
                 .execute(conn)?;
-        }
+            events::record(
+                ctx,
+                &proposal,
+                Event::ConcernResolvedFor(&concern.name),
+                Some(author.id),
+                Some(comment.id),
+            )
+        })?;
     }
 
     Ok(())
 }
 
-fn process_feedback_request(author: &GitHubUser, issue: &Issue, username: &str) -> DashResult<()> {
+fn process_feedback_request(
+    ctx: &Context<'_>,
+    author: &GitHubUser,
+    issue: &Issue,
+    username: &str,
+) -> DashResult<()> {
     use crate::domain::schema::rfc_feedback_request::dsl::*;
-    let conn = &*DB_POOL.get()?;
+    let conn = ctx.conn;
 
     // we'll just assume that this user exists...it's very unlikely that someone
     // will request feedback from a user who's *never* commented or committed
@@ -1156,17 +2778,43 @@ enum CommentType<'a> {
         FcpDisposition,
         &'a [(GitHubUser, FcpReviewRequest)],
         &'a [(GitHubUser, FcpConcern)],
+        /// Who delegated the delegated review requests.
+        &'a [GitHubUser],
+        /// Who put the proposal on hold and why, if it is on hold.
+        Option<(&'a GitHubUser, &'a str)>,
     ),
     FcpProposalCancelled(&'a GitHubUser),
-    FcpAllReviewedNoConcerns {
+    FcpDispositionChanged {
         author: &'a GitHubUser,
+        previous: FcpDisposition,
+        disposition: FcpDisposition,
         status_comment_id: i64,
-        added_label: bool,
     },
-    FcpWeekPassed {
+    FcpAllReviewedNoConcerns {
+        status_comment_id: i64,
+        fcp_end: NaiveDateTime,
+    },
+    FcpExtended {
+        author: &'a GitHubUser,
+        shortened: bool,
+        fcp_end: NaiveDateTime,
+    },
+    FcpPaused {
+        concern_name: &'a str,
+        concern_comment_id: i64,
+        /// How long the FCP has left, if it is to resume rather than restart.
+        remaining: Option<Duration>,
+    },
+    FcpHeld {
         author: &'a GitHubUser,
+        reason: &'a str,
+    },
+    FcpUnheld {
+        author: &'a GitHubUser,
+        fcp_end: NaiveDateTime,
+    },
+    FcpWeekPassed {
         status_comment_id: i64,
-        added_label: bool,
         disposition: FcpDisposition,
     },
     QuestionAsked {
@@ -1176,8 +2824,24 @@ enum CommentType<'a> {
         teams: BTreeSet<&'a str>,
     },
     Error {
-        message: &'a str,
+        messages: &'a [String],
+    },
+    Unauthorized {
+        author: &'a GitHubUser,
+        command: &'static str,
+        /// The teams whose members may use the command, or `None` for members of any team.
+        teams: Option<&'a BTreeSet<String>>,
+    },
+    /// A summary of the proposal and open polls on the issue, on request.
+    Status {
+        proposal: Option<&'a FcpProposal>,
+        reviewers: &'a [(GitHubUser, FcpReviewRequest)],
+        concerns: &'a [(GitHubUser, FcpConcern)],
+        polls: &'a [(Poll, Vec<(GitHubUser, PollResponseRequest)>)],
     },
+    Help,
+    /// Several replies to the same comment, posted together.
+    Combined(Vec<CommentType<'a>>),
 }
 
 impl<'a> RfcBotComment<'a> {
@@ -1191,14 +2855,6 @@ impl<'a> RfcBotComment<'a> {
         }
     }
 
-    fn couldnt_add_label<'b>(msg: &mut String, author: &'b GitHubUser, label: Label) {
-        msg.push_str("\n\n*psst @");
-        msg.push_str(&author.login);
-        msg.push_str(", I wasn't able to add the `");
-        msg.push_str(label.as_str());
-        msg.push_str("` label, please do so.*");
-    }
-
     fn format(issue: &Issue, comment_type: &CommentType<'_>) -> String {
         match *comment_type {
             CommentType::QuestionAsked {
@@ -1216,12 +2872,19 @@ impl<'a> RfcBotComment<'a> {
                 msg.push_str("\n\n");
                 format_ticky_boxes(
                     &mut msg,
-                    respondents.iter().map(|(m, rr)| (m, rr.responded)),
+                    respondents.iter().map(|(m, rr)| (m, None, rr.responded)),
                 );
                 msg
             }
 
-            CommentType::FcpProposed(initiator, disposition, reviewers, concerns) => {
+            CommentType::FcpProposed(
+                initiator,
+                disposition,
+                reviewers,
+                concerns,
+                delegators,
+                hold,
+            ) => {
                 let mut msg = String::from("@");
                 msg.push_str(&initiator.login);
                 msg.push_str(" has proposed to ");
@@ -1229,7 +2892,45 @@ impl<'a> RfcBotComment<'a> {
                 msg.push_str(" this. The next step is review by the rest of the tagged ");
                 msg.push_str("team members:\n\n");
 
-                format_ticky_boxes(&mut msg, reviewers.iter().map(|(m, rr)| (m, rr.reviewed)));
+                let groups = reviews_by_team(reviewers);
+                let per_team = groups.keys().any(Option::is_some);
+                if per_team {
+                    for (i, (team, group)) in groups.iter().enumerate() {
+                        if i > 0 {
+                            msg.push('\n');
+                        }
+                        msg.push_str("**");
+                        msg.push_str(team.unwrap_or("Other reviewers"));
+                        msg.push_str("** (once ");
+                        msg.extend(Itertools::intersperse(
+                            group_quorum_policies(issue, *team)
+                                .into_iter()
+                                .map(QuorumPolicy::describe),
+                            " and ".to_string(),
+                        ));
+                        msg.push_str("):\n\n");
+                        format_ticky_boxes(
+                            &mut msg,
+                            review_boxes(group.iter().copied(), delegators),
+                        );
+                    }
+                } else {
+                    format_ticky_boxes(&mut msg, review_boxes(reviewers, delegators));
+                }
+
+                let abstentions = abstentions(reviewers);
+                if !abstentions.is_empty() {
+                    msg.push_str("\nAbstaining:\n\n");
+                    for (member, reason) in abstentions {
+                        msg.push_str("* @");
+                        msg.push_str(&member.login);
+                        if let Some(reason) = reason {
+                            msg.push_str(": ");
+                            msg.push_str(reason);
+                        }
+                        msg.push('\n');
+                    }
+                }
 
                 if concerns.is_empty() {
                     msg.push_str("\nNo concerns currently listed.\n");
@@ -1237,15 +2938,17 @@ impl<'a> RfcBotComment<'a> {
                     msg.push_str("\nConcerns:\n\n");
                 }
 
-                for &(_, ref concern) in concerns {
+                // numbered, so that they can be resolved by number
+                for (number, (_, concern)) in (1..).zip(concerns.iter()) {
+                    msg.push_str(&number.to_string());
                     if let Some(resolved_comment_id) = concern.fk_resolved_comment {
-                        msg.push_str("* ~~");
+                        msg.push_str(". ~~");
                         msg.push_str(&concern.name);
                         msg.push_str("~~ resolved by ");
                         Self::add_comment_url(issue, &mut msg, resolved_comment_id);
                         msg.push_str("\n");
                     } else {
-                        msg.push_str("* ");
+                        msg.push_str(". ");
                         msg.push_str(&concern.name);
                         msg.push_str(" (");
                         Self::add_comment_url(issue, &mut msg, concern.fk_initiating_comment);
@@ -1253,8 +2956,28 @@ impl<'a> RfcBotComment<'a> {
                     }
                 }
 
-                msg.push_str("\nOnce a majority of reviewers approve (and at most 2 approvals are outstanding), ");
-                msg.push_str("this will enter its final comment period. ");
+                if let Some((holder, reason)) = hold {
+                    msg.push_str("\n**On hold** by @");
+                    msg.push_str(&holder.login);
+                    msg.push_str(": ");
+                    msg.push_str(reason);
+                    msg.push('\n');
+                }
+
+                if per_team {
+                    msg.push_str("\nOnce every team above has reached its quorum");
+                } else {
+                    msg.push_str("\nOnce ");
+                    msg.extend(Itertools::intersperse(
+                        quorum_policies(issue)
+                            .into_iter()
+                            .map(QuorumPolicy::describe),
+                        " and ".to_string(),
+                    ));
+                }
+                msg.push_str(", this will enter its final comment period, which lasts ");
+                msg.push_str(&fcp_length(issue, reviewers).num_days().to_string());
+                msg.push_str(" days. ");
                 msg.push_str("If you spot a major issue that hasn't been raised ");
                 msg.push_str("at any point in this process, please speak up!\n");
 
@@ -1262,9 +2985,9 @@ impl<'a> RfcBotComment<'a> {
                     msg.push_str("\ncc @rust-lang/lang-advisors: FCP proposed for lang, please feel free to register concerns.");
                 }
 
-                msg.push_str("\nSee [this document](");
-                msg.push_str("https://github.com/rust-lang/rfcbot-rs/blob/master/README.md");
-                msg.push_str(") for info about what commands tagged team members can give me.");
+                msg.push_str("\nUse `");
+                msg.push_str(RFC_BOT_MENTIONS[0]);
+                msg.push_str(" help` to see what commands tagged team members can give me.");
 
                 msg
             }
@@ -1273,10 +2996,28 @@ impl<'a> RfcBotComment<'a> {
                 format!("@{} proposal cancelled.", initiator.login)
             }
 
-            CommentType::FcpAllReviewedNoConcerns {
+            CommentType::FcpDispositionChanged {
                 author,
+                previous,
+                disposition,
+                status_comment_id,
+            } => {
+                let mut msg = String::from("@");
+                msg.push_str(&author.login);
+                msg.push_str(" has changed the proposal to **");
+                msg.push_str(previous.repr());
+                msg.push_str("** this into a proposal to **");
+                msg.push_str(disposition.repr());
+                msg.push_str("** it. The reviews given so far were for the old proposal, so ");
+                msg.push_str("they have been reset: please [review it again](");
+                Self::add_comment_url(issue, &mut msg, status_comment_id);
+                msg.push_str(").");
+                msg
+            }
+
+            CommentType::FcpAllReviewedNoConcerns {
                 status_comment_id,
-                added_label,
+                fcp_end,
             } => {
                 let mut msg = String::new();
 
@@ -1285,16 +3026,77 @@ impl<'a> RfcBotComment<'a> {
                 Self::add_comment_url(issue, &mut msg, status_comment_id);
                 msg.push_str("). :bell:");
 
-                if !added_label {
-                    Self::couldnt_add_label(&mut msg, author, Label::FCP);
+                msg.push_str("\n\nThe final comment period will end on ");
+                Self::add_date(&mut msg, fcp_end);
+                msg.push('.');
+
+                msg
+            }
+
+            CommentType::FcpExtended {
+                author,
+                shortened,
+                fcp_end,
+            } => {
+                let mut msg = String::from("@");
+                msg.push_str(&author.login);
+                msg.push_str(if shortened {
+                    " has shortened"
+                } else {
+                    " has extended"
+                });
+                msg.push_str(" the final comment period, which will now end on ");
+                Self::add_date(&mut msg, fcp_end);
+                msg.push('.');
+                msg
+            }
+
+            CommentType::FcpPaused {
+                concern_name,
+                concern_comment_id,
+                remaining,
+            } => {
+                let mut msg = String::new();
+                msg.push_str(":pause_button: **The final comment period is paused** ");
+                msg.push_str("because of a new concern, [");
+                msg.push_str(concern_name);
+                msg.push_str("](");
+                Self::add_comment_url(issue, &mut msg, concern_comment_id);
+                msg.push_str("). :pause_button:\n\nOnce every concern is resolved, ");
+                match remaining {
+                    Some(remaining) => {
+                        msg.push_str("the final comment period will resume with ");
+                        Self::add_duration(&mut msg, remaining);
+                        msg.push_str(" left.");
+                    }
+                    None => {
+                        msg.push_str("the final comment period will restart from the beginning.")
+                    }
                 }
+                msg
+            }
 
+            CommentType::FcpHeld { author, reason } => {
+                let mut msg = String::from("@");
+                msg.push_str(&author.login);
+                msg.push_str(" has put the final comment period on hold: ");
+                msg.push_str(reason);
+                msg.push_str("\n\nIts clock is stopped until someone uses `");
+                msg.push_str(RFC_BOT_MENTIONS[0]);
+                msg.push_str(" unhold`.");
+                msg
+            }
+
+            CommentType::FcpUnheld { author, fcp_end } => {
+                let mut msg = String::from("@");
+                msg.push_str(&author.login);
+                msg.push_str(" has taken the final comment period off hold. It will now end on ");
+                Self::add_date(&mut msg, fcp_end);
+                msg.push('.');
                 msg
             }
 
             CommentType::FcpWeekPassed {
-                author,
-                added_label,
                 status_comment_id,
                 disposition,
             } => {
@@ -1324,18 +3126,167 @@ impl<'a> RfcBotComment<'a> {
                     _ => {}
                 }
 
-                if !added_label {
-                    Self::couldnt_add_label(&mut msg, author, Label::FFCP);
+                msg
+            }
+
+            CommentType::Error { messages } => {
+                let mut msg = String::from("Error encountered:\n");
+                if let [message] = messages {
+                    msg.push_str(message);
+                } else {
+                    for message in messages {
+                        msg.push_str("* ");
+                        msg.push_str(message);
+                        msg.push('\n');
+                    }
                 }
+                msg
+            }
 
+            CommentType::Unauthorized {
+                author,
+                command,
+                teams,
+            } => {
+                let mut msg = String::from("@");
+                msg.push_str(&author.login);
+                msg.push_str(", I've ignored your `");
+                msg.push_str(command);
+                msg.push_str("` command, because it can only be used by members of ");
+                match teams {
+                    None => msg.push_str("one of the teams I know about."),
+                    Some(teams) if teams.is_empty() => msg.push_str(
+                        "the teams labeled on this issue, and it doesn't have any team labels.",
+                    ),
+                    Some(teams) => {
+                        msg.push_str("the teams labeled on this issue: ");
+                        msg.extend(Itertools::intersperse(
+                            teams.iter().map(String::as_str),
+                            ", ",
+                        ));
+                        msg.push('.');
+                    }
+                }
                 msg
             }
 
-            CommentType::Error { message } => {
-                let mut msg = String::from("Error encountered:\n");
-                msg.push_str(message);
+            CommentType::Status {
+                proposal,
+                reviewers,
+                concerns,
+                polls,
+            } => {
+                let mut msg = String::new();
+
+                if let Some(proposal) = proposal {
+                    msg.push_str("There is a [proposal](");
+                    Self::add_comment_url(issue, &mut msg, proposal.fk_bot_tracking_comment);
+                    msg.push_str(") to **");
+                    msg.push_str(&proposal.disposition);
+                    msg.push_str("** this.\n\n");
+
+                    match (proposal.fcp_start, fcp_end(proposal, issue)) {
+                        (Some(_), Some(end)) if proposal.fcp_closed => {
+                            msg.push_str("Its final comment period ended on ");
+                            Self::add_date(&mut msg, end);
+                            msg.push_str(".\n");
+                        }
+                        (Some(start), Some(end)) => {
+                            msg.push_str("Its final comment period started on ");
+                            Self::add_date(&mut msg, start);
+                            if proposal.held_at.is_none() {
+                                msg.push_str(" and is due to end on ");
+                                Self::add_date(&mut msg, end);
+                            }
+                            msg.push_str(".\n");
+                        }
+                        _ => {
+                            // logins aren't mentioned, so that asking doesn't ping everyone
+                            let pending = reviewers
+                                .iter()
+                                .filter(|(_, review)| !review.reviewed && !review.abstained)
+                                .map(|(member, _)| format!("`{}`", member.login))
+                                .unique()
+                                .collect::<Vec<_>>();
+                            if pending.is_empty() {
+                                msg.push_str("Everyone has reviewed it.\n");
+                            } else {
+                                msg.push_str("Waiting on review from: ");
+                                msg.push_str(&pending.join(", "));
+                                msg.push_str(".\n");
+                            }
+                            let abstaining = abstentions(reviewers)
+                                .into_iter()
+                                .map(|(member, _)| format!("`{}`", member.login))
+                                .collect::<Vec<_>>();
+                            if !abstaining.is_empty() {
+                                msg.push_str("Abstaining: ");
+                                msg.push_str(&abstaining.join(", "));
+                                msg.push_str(".\n");
+                            }
+                        }
+                    }
+
+                    if let (Some(since), Some(reason)) = (proposal.held_at, &proposal.hold_reason) {
+                        msg.push_str("It has been on hold since ");
+                        Self::add_date(&mut msg, since);
+                        msg.push_str(": ");
+                        msg.push_str(reason);
+                        msg.push('\n');
+                    }
+
+                    // numbered as in the status comment
+                    let unresolved = (1..)
+                        .zip(concerns.iter())
+                        .filter(|(_, (_, concern))| concern.fk_resolved_comment.is_none())
+                        .collect::<Vec<_>>();
+                    if unresolved.is_empty() {
+                        msg.push_str("\nNo unresolved concerns.\n");
+                    } else {
+                        msg.push_str("\nUnresolved concerns:\n\n");
+                        for (number, (_, concern)) in unresolved {
+                            msg.push_str("* ");
+                            msg.push_str(&number.to_string());
+                            msg.push_str(": ");
+                            msg.push_str(&concern.name);
+                            msg.push_str(" (");
+                            Self::add_comment_url(issue, &mut msg, concern.fk_initiating_comment);
+                            msg.push_str(")\n");
+                        }
+                    }
+                }
+
+                for (survey, respondents) in polls {
+                    if !msg.is_empty() {
+                        msg.push('\n');
+                    }
+                    msg.push_str("There is a [poll](");
+                    Self::add_comment_url(issue, &mut msg, survey.fk_bot_tracking_comment);
+                    msg.push_str(") on: \n > ");
+                    msg.push_str(&survey.poll_question);
+                    msg.push_str("\n\n");
+                    let pending = respondents
+                        .iter()
+                        .filter(|(_, response)| !response.responded)
+                        .map(|(member, _)| format!("`{}`", member.login))
+                        .collect::<Vec<_>>();
+                    msg.push_str("Waiting on responses from: ");
+                    msg.push_str(&pending.join(", "));
+                    msg.push_str(".\n");
+                }
+
+                if msg.is_empty() {
+                    msg.push_str("There is no FCP proposal or open poll on this issue.");
+                }
                 msg
             }
+
+            CommentType::Help => command::help(),
+
+            CommentType::Combined(ref replies) => replies
+                .iter()
+                .map(|reply| Self::format(issue, reply))
+                .join("\n\n"),
         }
     }
 
@@ -1354,6 +3305,25 @@ impl<'a> RfcBotComment<'a> {
         msg.push_str(&url);
     }
 
+    fn add_date(msg: &mut String, date: NaiveDateTime) {
+        msg.push_str(&date.format("%Y-%m-%d").to_string());
+        msg.push_str(" (UTC)");
+    }
+
+    fn add_duration(msg: &mut String, duration: Duration) {
+        let (amount, unit) = if duration >= Duration::days(1) {
+            (duration.num_days(), "day")
+        } else {
+            (duration.num_hours(), "hour")
+        };
+        msg.push_str(&amount.to_string());
+        msg.push(' ');
+        msg.push_str(unit);
+        if amount != 1 {
+            msg.push('s');
+        }
+    }
+
     fn maybe_add_pfcp_label(&self) {
         if let CommentType::FcpProposed(_, disposition, ..) = self.comment_type {
             let _ = self.issue.add_label(Label::PFCP);
@@ -1362,7 +3332,17 @@ impl<'a> RfcBotComment<'a> {
         }
     }
 
-    fn post(&self, existing_comment: Option<i64>) -> DashResult<CommentFromJson> {
+    /// Post the comment, or edit an existing comment to say it. While replaying, GitHub has seen
+    /// the comment already.
+    fn post(&self, ctx: &Context<'_>, existing_comment: Option<i64>) -> DashResult<()> {
+        if ctx.is_replaying() {
+            return Ok(());
+        }
+        self.send(existing_comment)?;
+        Ok(())
+    }
+
+    fn send(&self, existing_comment: Option<i64>) -> DashResult<CommentFromJson> {
         use crate::config::CONFIG;
 
         if CONFIG.post_comments {
@@ -1391,13 +3371,481 @@ impl<'a> RfcBotComment<'a> {
     }
 }
 
+/// The reviewers who have abstained, with their reasons. Members of several teams are only listed
+/// once.
+fn abstentions(reviewers: &[(GitHubUser, FcpReviewRequest)]) -> Vec<(&GitHubUser, Option<&str>)> {
+    reviewers
+        .iter()
+        .filter(|(_, review)| review.abstained)
+        .unique_by(|(member, _)| member.id)
+        .map(|(member, review)| (member, review.abstain_reason.as_deref()))
+        .collect()
+}
+
+/// The boxes for the reviewers who haven't abstained.
+fn review_boxes<'a>(
+    reviews: impl IntoIterator<Item = &'a (GitHubUser, FcpReviewRequest)>,
+    delegators: &'a [GitHubUser],
+) -> impl Iterator<Item = (&'a GitHubUser, Option<&'a GitHubUser>, bool)> {
+    reviews
+        .into_iter()
+        .filter(|(_, review)| !review.abstained)
+        .map(move |(member, review)| {
+            let delegator = review
+                .fk_delegated_from
+                .and_then(|id| delegators.iter().find(|d| d.id == id));
+            (member, delegator, review.reviewed)
+        })
+}
+
+/// List reviewers with a box each, along with who delegated their review to them if anyone did.
 fn format_ticky_boxes<'a>(
     msg: &mut String,
-    reviewers: impl Iterator<Item = (&'a GitHubUser, bool)>,
+    reviewers: impl IntoIterator<Item = (&'a GitHubUser, Option<&'a GitHubUser>, bool)>,
 ) {
-    for (member, reviewed) in reviewers {
+    for (member, delegator, reviewed) in reviewers {
         msg.push_str(if reviewed { "* [x] @" } else { "* [ ] @" });
-        msg.push_str(&member.login);
+        if let Some(delegator) = delegator {
+            msg.push_str(&delegator.login);
+            msg.push(' ');
+            msg.push_str(DELEGATED_TO);
+            msg.push_str(&member.login);
+            msg.push(')');
+        } else {
+            msg.push_str(&member.login);
+        }
         msg.push('\n');
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::rfcbot::FcpEvent;
+    use crate::domain::schema::fcp_event;
+    use crate::github::tests::Fixture;
+    use crate::teams::test::TEST_SETUP;
+
+    fn concern(conn: &PgConnection, proposal: &FcpProposal, comment: &IssueComment, name: &str) {
+        diesel::insert_into(fcp_concern::table)
+            .values(&NewFcpConcern {
+                fk_proposal: proposal.id,
+                fk_initiator: comment.fk_user,
+                fk_resolved_comment: None,
+                name,
+                fk_initiating_comment: comment.id,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn review_request(conn: &PgConnection, proposal: &FcpProposal, user: &GitHubUser, done: bool) {
+        diesel::insert_into(fcp_review_request::table)
+            .values(&NewFcpReviewRequest {
+                fk_proposal: proposal.id,
+                fk_reviewer: user.id,
+                reviewed: done,
+                team_label: None,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn reviewed(conn: &PgConnection, proposal: &FcpProposal, user: &GitHubUser) -> bool {
+        fcp_review_request::table
+            .filter(fcp_review_request::fk_proposal.eq(proposal.id))
+            .filter(fcp_review_request::fk_reviewer.eq(user.id))
+            .select(fcp_review_request::reviewed)
+            .first(conn)
+            .unwrap()
+    }
+
+    /// The status comment of `proposal` before and after an edit to its boxes.
+    fn status_edit(
+        conn: &PgConnection,
+        proposal: &FcpProposal,
+        before: &str,
+        after: &str,
+    ) -> (IssueComment, IssueComment) {
+        let status = issuecomment::table
+            .find(proposal.fk_bot_tracking_comment)
+            .first::<IssueComment>(conn)
+            .unwrap();
+        let previous = IssueComment {
+            body: before.to_string(),
+            ..status.clone()
+        };
+        let comment = IssueComment {
+            body: after.to_string(),
+            ..status
+        };
+        (previous, comment)
+    }
+
+    fn events(conn: &PgConnection, proposal: &FcpProposal) -> Vec<(String, Option<String>)> {
+        fcp_event::table
+            .filter(fcp_event::fk_proposal.eq(proposal.id))
+            .order(fcp_event::id)
+            .load::<FcpEvent>(conn)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.event, e.detail))
+            .collect()
+    }
+
+    #[test]
+    fn new_comment_commands() {
+        let body = "@rfcbot reviewed\n@rfcbot fcp frobnicate\n@rfcbot concern naming";
+        let edited = edited_commands(&TEST_SETUP, None, body);
+
+        assert_eq!(edited.commands.len(), 3);
+        assert_eq!(edited.commands[0], Ok(RfcBotCommand::Reviewed));
+        assert!(edited.commands[1].is_err());
+        assert_eq!(edited.commands[2], Ok(RfcBotCommand::NewConcern("naming")));
+        assert!(edited.removed_concerns.is_empty());
+    }
+
+    #[test]
+    fn edit_runs_added_commands() {
+        let before = "@rfcbot reviewed\n@rfcbot concern naming";
+        let after = "@rfcbot reviewed\n@rfcbot concern naming\n@rfcbot concern speed";
+        let edited = edited_commands(&TEST_SETUP, Some(before), after);
+
+        assert_eq!(
+            edited.commands,
+            vec![Ok(RfcBotCommand::NewConcern("speed"))]
+        );
+        assert!(edited.removed_concerns.is_empty());
+
+        // nothing changed, nothing to do
+        let edited = edited_commands(&TEST_SETUP, Some(before), before);
+        assert!(edited.commands.is_empty());
+        assert!(edited.removed_concerns.is_empty());
+    }
+
+    #[test]
+    fn edit_removes_concerns() {
+        let before = "@rfcbot concern naming\n@rfcbot concern speed";
+        let after = "@rfcbot concern Naming.\n\nNever mind about the speed.";
+        let edited = edited_commands(&TEST_SETUP, Some(before), after);
+
+        // the concern's name was only respelled, so it's still raised
+        assert_eq!(edited.removed_concerns, vec!["speed"]);
+    }
+
+    #[test]
+    fn edit_reports_new_errors_only() {
+        let before = "@rfcbot fcp frobnicate";
+        let after = "@rfcbot fcp frobnicate\n@rfcbot reviwed";
+        let edited = edited_commands(&TEST_SETUP, Some(before), after);
+
+        let fresh = edited_commands(&TEST_SETUP, None, "@rfcbot reviwed");
+        assert_eq!(edited.commands, fresh.commands);
+        assert_eq!(edited.commands.len(), 1);
+    }
+
+    #[test]
+    fn deleted_comment_withdraws_its_concerns() {
+        let fx = Fixture::new();
+        let (conn, ctx) = (&fx.conn, &fx.ctx());
+        let deleted = fx.comment(&fx.alice, "@rfcbot concern naming");
+        let kept = fx.comment(&fx.alice, "@rfcbot concern speed");
+        concern(conn, &fx.proposal, &deleted, "naming");
+        concern(conn, &fx.proposal, &kept, "speed");
+
+        forget_comment(ctx, &deleted).unwrap();
+
+        let concerns = list_concerns_with_authors(ctx, fx.proposal.id).unwrap();
+        assert_eq!(
+            concerns.iter().map(|(_, c)| &*c.name).collect::<Vec<_>>(),
+            vec!["speed"]
+        );
+        assert_eq!(
+            events(conn, &fx.proposal),
+            vec![("concern-withdrawn".to_string(), Some("naming".to_string()))]
+        );
+        assert_eq!(
+            issuecomment::table
+                .find(deleted.id)
+                .first::<IssueComment>(conn)
+                .optional()
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn deleted_status_comment_of_finished_fcp_stays() {
+        let fx = Fixture::new();
+        let (conn, ctx) = (&fx.conn, &fx.ctx());
+        diesel::update(fcp_proposal::table.find(fx.proposal.id))
+            .set(fcp_proposal::fcp_closed.eq(true))
+            .execute(conn)
+            .unwrap();
+        let status = issuecomment::table
+            .find(fx.proposal.fk_bot_tracking_comment)
+            .first::<IssueComment>(conn)
+            .unwrap();
+
+        // nothing is posted, or this would need the teams to describe the proposal
+        forget_comment(ctx, &status).unwrap();
+
+        let after = fcp_proposal::table
+            .find(fx.proposal.id)
+            .first::<FcpProposal>(conn)
+            .unwrap();
+        assert_eq!(after.fk_bot_tracking_comment, status.id);
+        assert!(issuecomment::table
+            .find(status.id)
+            .first::<IssueComment>(conn)
+            .is_ok());
+    }
+
+    #[test]
+    fn bot_rerender_of_status_comment_is_ignored() {
+        let fx = Fixture::new();
+        let (conn, ctx, alice, proposal) = (&fx.conn, &fx.ctx(), &fx.alice, &fx.proposal);
+        let bot = fx.user(-102, "rfcbot");
+        review_request(conn, proposal, alice, false);
+
+        // a render from before alice withdrew her review with a command
+        let (previous, comment) = status_edit(conn, proposal, "* [ ] @alice", "* [x] @alice");
+        apply_ticky_box_edits(ctx, &previous, &comment, Some(&bot)).unwrap();
+
+        assert!(!reviewed(conn, proposal, alice));
+        assert_eq!(events(conn, proposal), vec![]);
+    }
+
+    #[test]
+    fn stale_ticky_boxes_are_ignored() {
+        let fx = Fixture::new();
+        let (conn, ctx, alice, proposal) = (&fx.conn, &fx.ctx(), &fx.alice, &fx.proposal);
+        let bob = fx.user(-102, "bob");
+        review_request(conn, proposal, alice, true);
+        review_request(conn, proposal, &bob, false);
+
+        // the stored status comment lags behind alice's review with a command
+        let (previous, comment) = status_edit(
+            conn,
+            proposal,
+            "* [ ] @alice\n* [ ] @bob",
+            "* [x] @alice\n* [ ] @bob",
+        );
+        apply_ticky_box_edits(ctx, &previous, &comment, None).unwrap();
+        assert!(reviewed(conn, proposal, alice));
+
+        // and behind bob's withdrawal
+        let (previous, comment) = status_edit(
+            conn,
+            proposal,
+            "* [x] @alice\n* [x] @bob",
+            "* [x] @alice\n* [ ] @bob",
+        );
+        apply_ticky_box_edits(ctx, &previous, &comment, Some(&bob)).unwrap();
+        assert!(!reviewed(conn, proposal, &bob));
+
+        assert_eq!(events(conn, proposal), vec![]);
+
+        let reviewed_in_db = std::iter::once("alice").collect();
+        assert_eq!(
+            ticky_box_edits(proposal.id, &previous, &comment, &reviewed_in_db),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn unticking_a_box_withdraws_the_review() {
+        let fx = Fixture::new();
+        let (conn, ctx, alice, proposal) = (&fx.conn, &fx.ctx(), &fx.alice, &fx.proposal);
+        let bob = fx.user(-102, "bob");
+        review_request(conn, proposal, alice, true);
+        review_request(conn, proposal, &bob, true);
+
+        let (previous, comment) = status_edit(
+            conn,
+            proposal,
+            "* [x] @alice\n* [x] @bob",
+            "* [ ] @alice\n* [x] @bob",
+        );
+        apply_ticky_box_edits(ctx, &previous, &comment, Some(alice)).unwrap();
+
+        assert!(!reviewed(conn, proposal, alice));
+        assert!(reviewed(conn, proposal, &bob));
+        assert_eq!(
+            events(conn, proposal),
+            vec![("unreviewed".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn abstaining() {
+        let fx = Fixture::new();
+        let (conn, ctx, alice, proposal) = (&fx.conn, &fx.ctx(), &fx.alice, &fx.proposal);
+        let bob = fx.user(-102, "bob");
+        review_request(conn, proposal, alice, true);
+        let comment = fx.comment(alice, "@rfcbot abstain busy");
+
+        process_abstain(ctx, alice, &fx.issue, &comment, Some("busy")).unwrap();
+
+        let requests = list_review_requests(ctx, proposal.id).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(!requests[0].1.reviewed);
+        assert!(requests[0].1.abstained);
+        assert_eq!(requests[0].1.abstain_reason.as_deref(), Some("busy"));
+        assert_eq!(
+            events(conn, proposal),
+            vec![("abstained".to_string(), Some("busy".to_string()))]
+        );
+
+        let comment = fx.comment(&bob, "@rfcbot abstain");
+        match process_abstain(ctx, &bob, &fx.issue, &comment, None) {
+            Err(DashError::CommentableError(why)) => {
+                assert_eq!(why, "You aren't reviewing this proposal.")
+            }
+            other => panic!("bob abstained without being asked to review: {:?}", other),
+        }
+        assert_eq!(events(conn, proposal).len(), 1);
+    }
+
+    #[test]
+    fn abstentions_dont_count() {
+        let request = |id, reviewed, abstained| {
+            let user = GitHubUser {
+                id,
+                login: format!("reviewer{}", id),
+            };
+            let request = FcpReviewRequest {
+                id,
+                fk_proposal: 1,
+                fk_reviewer: id,
+                reviewed,
+                team_label: None,
+                abstained,
+                abstain_reason: None,
+                fk_delegated_from: None,
+            };
+            (user, request)
+        };
+        let requests = vec![
+            request(1, true, false),
+            request(2, true, false),
+            request(3, false, true),
+            request(4, false, true),
+            request(5, false, false),
+        ];
+        let group = requests.iter().collect::<Vec<_>>();
+
+        assert_eq!(review_counts(&group), (2, 1));
+        assert!(QuorumPolicy::Majority.is_met(2, 1));
+
+        // only abstentions
+        let group = group[2..4].to_vec();
+        assert_eq!(review_counts(&group), (0, 0));
+        assert!(!QuorumPolicy::Unanimous.is_met(0, 0));
+    }
+
+    #[test]
+    fn changing_disposition_resets_reviews() {
+        let fx = Fixture::new();
+        let (conn, ctx, alice) = (&fx.conn, &fx.ctx(), &fx.alice);
+        let bob = fx.user(-102, "bob");
+        let carol = fx.user(-103, "carol");
+        let mut proposal = fx.proposal.clone();
+        review_request(conn, &proposal, alice, true);
+        review_request(conn, &proposal, &bob, false);
+        review_request(conn, &proposal, &carol, true);
+        let comment = fx.comment(&bob, "@rfcbot fcp close");
+
+        let previous =
+            update_disposition(ctx, &bob, &comment, &mut proposal, FcpDisposition::Close);
+        assert_eq!(previous.unwrap(), Some(FcpDisposition::Merge));
+
+        let changed = fcp_proposal::table
+            .find(proposal.id)
+            .first::<FcpProposal>(conn)
+            .unwrap();
+        assert_eq!(changed.disposition, "close");
+        assert!(!reviewed(conn, &proposal, alice));
+        assert!(reviewed(conn, &proposal, &bob));
+        assert!(!reviewed(conn, &proposal, &carol));
+        assert_eq!(
+            events(conn, &proposal),
+            vec![
+                ("disposition-changed".to_string(), Some("close".to_string())),
+                ("reviewed".to_string(), None),
+            ]
+        );
+
+        // proposing what's already proposed changes nothing
+        let previous =
+            update_disposition(ctx, &bob, &comment, &mut proposal, FcpDisposition::Close);
+        assert_eq!(previous.unwrap(), None);
+        assert_eq!(events(conn, &proposal).len(), 2);
+    }
+
+    #[test]
+    fn changing_disposition_is_refused() {
+        let fx = Fixture::new();
+        let (conn, ctx) = (&fx.conn, &fx.ctx());
+        let bob = fx.user(-102, "bob");
+        let mut proposal = fx.proposal.clone();
+        let comment = fx.comment(&bob, "@rfcbot fcp postpone");
+
+        let refusal = |proposal: &FcpProposal| {
+            let mut proposal = proposal.clone();
+            match update_disposition(ctx, &bob, &comment, &mut proposal, FcpDisposition::Postpone) {
+                Err(DashError::CommentableError(why)) => why,
+                other => panic!("the disposition was changed: {:?}", other),
+            }
+        };
+
+        assert_eq!(
+            refusal(&proposal),
+            "Only the reviewers of the proposal to merge this can change what it proposes."
+        );
+
+        review_request(conn, &proposal, &bob, false);
+        proposal.fcp_start = Some(ctx.now());
+        assert!(refusal(&proposal)
+            .starts_with("The final comment period to merge this has already started"));
+
+        proposal.fcp_closed = true;
+        assert_eq!(
+            refusal(&proposal),
+            "The final comment period to merge this has already finished, so it can't be changed."
+        );
+
+        let unchanged = fcp_proposal::table
+            .find(proposal.id)
+            .first::<FcpProposal>(conn)
+            .unwrap();
+        assert_eq!(unchanged.disposition, "merge");
+        assert_eq!(events(conn, &proposal), vec![]);
+    }
+
+    #[test]
+    fn concern_leaves_finished_fcp_alone() {
+        let fx = Fixture::new();
+        let (conn, ctx, alice, proposal) = (&fx.conn, &fx.ctx(), &fx.alice, &fx.proposal);
+        let finished = diesel::update(fcp_proposal::table.find(proposal.id))
+            .set((
+                fcp_proposal::fcp_start.eq(Some(ctx.now() - Duration::days(10))),
+                fcp_proposal::fcp_closed.eq(true),
+            ))
+            .get_result::<FcpProposal>(conn)
+            .unwrap();
+        let comment = fx.comment(alice, "@rfcbot concern naming");
+
+        process_new_concern(ctx, alice, &fx.issue, &comment, "naming").unwrap();
+
+        let after = fcp_proposal::table
+            .find(proposal.id)
+            .first::<FcpProposal>(conn)
+            .unwrap();
+        assert_eq!(after, finished);
+        assert_eq!(
+            events(conn, proposal),
+            vec![("concern-raised".to_string(), Some("naming".to_string()))]
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! An outbox for the GitHub side effects of FCP state changes.
+//!
+//! Actions are recorded in the `github_action` table in the same transaction as the state change
+//! that causes them, and are performed later by `drain`, which retries failed actions. This way a
+//! crash or a GitHub outage can't leave an FCP marked as finished without it being announced.
+
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+
+use super::GH;
+use crate::config::CONFIG;
+use crate::domain::github::Issue;
+use crate::domain::rfcbot::{GitHubAction, NewGitHubAction};
+use crate::domain::schema::{github_action, issue};
+use crate::error::{DashError, DashResult};
+use crate::github::command::Label;
+use crate::DB_POOL;
+
+const DRAIN_OUTBOX_EVERY_MIN: u64 = 1;
+
+/// How many times we try to perform an action before giving up on it.
+const MAX_ATTEMPTS: i32 = 10;
+
+const ACTION_COMMENT: &str = "comment";
+const ACTION_EDIT_COMMENT: &str = "edit-comment";
+const ACTION_ADD_LABEL: &str = "add-label";
+const ACTION_REMOVE_LABEL: &str = "remove-label";
+const ACTION_CLOSE: &str = "close";
+
+lazy_static! {
+    static ref OUTBOX_LOCK: Mutex<()> = Mutex::new(());
+}
+
+#[derive(Clone)]
+pub enum Action<'a> {
+    Comment(&'a str),
+    EditComment(i64, &'a str),
+    AddLabel(Label),
+    RemoveLabel(Label),
+    Close,
+}
+
+impl Action<'_> {
+    fn to_new<'a>(&'a self, issue: &Issue) -> NewGitHubAction<'a> {
+        let mut new = NewGitHubAction {
+            fk_issue: issue.id,
+            action: "",
+            body: None,
+            label: None,
+            fk_comment: None,
+            created_at: Utc::now().naive_utc(),
+        };
+        match *self {
+            Action::Comment(body) => {
+                new.action = ACTION_COMMENT;
+                new.body = Some(body);
+            }
+            Action::EditComment(comment_id, body) => {
+                new.action = ACTION_EDIT_COMMENT;
+                new.body = Some(body);
+                new.fk_comment = Some(comment_id);
+            }
+            Action::AddLabel(label) => {
+                new.action = ACTION_ADD_LABEL;
+                new.label = Some(label.as_str());
+            }
+            Action::RemoveLabel(label) => {
+                new.action = ACTION_REMOVE_LABEL;
+                new.label = Some(label.as_str());
+            }
+            Action::Close => new.action = ACTION_CLOSE,
+        }
+        new
+    }
+}
+
+/// Record actions to be performed on an issue. Call this within the transaction which makes the
+/// state change the actions are announcing.
+pub fn enqueue(conn: &PgConnection, issue: &Issue, actions: &[Action<'_>]) -> DashResult<()> {
+    let new_actions = actions
+        .iter()
+        .map(|action| action.to_new(issue))
+        .collect::<Vec<_>>();
+
+    diesel::insert_into(github_action::table)
+        .values(&new_actions)
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Point pending edits of a comment at the comment which replaces it.
+pub fn redirect_edits(conn: &PgConnection, from: i64, to: i64) -> DashResult<()> {
+    diesel::update(
+        github_action::table
+            .filter(github_action::fk_comment.eq(from))
+            .filter(github_action::completed_at.is_null()),
+    )
+    .set(github_action::fk_comment.eq(to))
+    .execute(conn)?;
+
+    Ok(())
+}
+
+/// Are there actions on this issue which are still to be performed?
+pub fn has_pending(conn: &PgConnection, issue: &Issue) -> DashResult<bool> {
+    let pending = github_action::table
+        .filter(github_action::fk_issue.eq(issue.id))
+        .filter(github_action::completed_at.is_null())
+        .filter(github_action::attempts.lt(MAX_ATTEMPTS));
+
+    Ok(diesel::select(diesel::dsl::exists(pending)).get_result(conn)?)
+}
+
+pub fn start_outbox_thread() {
+    let _ = crate::utils::spawn_thread("GitHub outbox", DRAIN_OUTBOX_EVERY_MIN, drain);
+}
+
+/// Perform all pending actions, in the order they were recorded.
+///
+/// If an action fails, the remaining actions for the same issue are left for the next attempt so
+/// that they still happen in order.
+pub fn drain() -> DashResult<()> {
+    let _in_progress_marker = OUTBOX_LOCK.lock();
+    let conn = &*DB_POOL.get()?;
+
+    drain_with(conn, perform)
+}
+
+fn drain_with<F>(conn: &PgConnection, mut perform: F) -> DashResult<()>
+where
+    F: FnMut(&Issue, &GitHubAction) -> DashResult<()>,
+{
+    let pending = github_action::table
+        .filter(github_action::completed_at.is_null())
+        .filter(github_action::attempts.lt(MAX_ATTEMPTS))
+        .order(github_action::id)
+        .load::<GitHubAction>(conn)?;
+
+    let mut blocked_issues = BTreeSet::new();
+
+    for mut action in pending {
+        if blocked_issues.contains(&action.fk_issue) {
+            continue;
+        }
+
+        let result = issue::table
+            .find(action.fk_issue)
+            .first::<Issue>(conn)
+            .map_err(DashError::from)
+            .and_then(|issue| perform(&issue, &action));
+
+        match result {
+            Ok(()) => action.completed_at = Some(Utc::now().naive_utc()),
+            Err(why) => {
+                blocked_issues.insert(action.fk_issue);
+                action.attempts += 1;
+                action.last_error = Some(format!("{:?}", why));
+                if action.attempts >= MAX_ATTEMPTS {
+                    error!(
+                        "Giving up on GitHub action {} after {} attempts: {:?}",
+                        action.id, action.attempts, why
+                    );
+                } else {
+                    warn!("Unable to perform GitHub action {}: {:?}", action.id, why);
+                }
+            }
+        }
+
+        diesel::update(github_action::table.find(action.id))
+            .set(&action)
+            .execute(conn)?;
+    }
+
+    Ok(())
+}
+
+fn perform(issue: &Issue, action: &GitHubAction) -> DashResult<()> {
+    if !CONFIG.post_comments {
+        info!(
+            "Skipping {} on {}#{}, GitHub actions are disabled.",
+            action.action, issue.repository, issue.number
+        );
+        return Ok(());
+    }
+
+    if comments_on_closed_issue(issue, action) {
+        info!(
+            "Skipping {} on {}#{}, the issue is no longer open.",
+            action.action, issue.repository, issue.number
+        );
+        return Ok(());
+    }
+
+    let body = || action.body.as_deref().ok_or(DashError::Misc(None));
+    let label = || action.label.as_deref().ok_or(DashError::Misc(None));
+
+    match &*action.action {
+        ACTION_COMMENT => {
+            GH.new_comment(&issue.repository, issue.number, body()?)?;
+        }
+        ACTION_EDIT_COMMENT => {
+            let comment_id = action.fk_comment.ok_or(DashError::Misc(None))?;
+            GH.edit_comment(&issue.repository, comment_id, body()?)?;
+        }
+        ACTION_ADD_LABEL => GH.add_label(&issue.repository, issue.number, label()?)?,
+        ACTION_REMOVE_LABEL => GH.remove_label(&issue.repository, issue.number, label()?)?,
+        ACTION_CLOSE => GH.close_issue(&issue.repository, issue.number)?,
+        other => throw!(DashError::Misc(Some(format!(
+            "unknown GitHub action: {}",
+            other
+        )))),
+    }
+
+    Ok(())
+}
+
+/// Is this a comment on an issue which has been closed by the time we get to it? Those are left
+/// out, the same as if we had tried to post them right away.
+fn comments_on_closed_issue(issue: &Issue, action: &GitHubAction) -> bool {
+    let comments = action.action == ACTION_COMMENT || action.action == ACTION_EDIT_COMMENT;
+    comments && !issue.open
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::github::tests::{test_connection, test_issue};
+
+    fn actions_for(conn: &PgConnection, issue: &Issue) -> Vec<GitHubAction> {
+        github_action::table
+            .filter(github_action::fk_issue.eq(issue.id))
+            .order(github_action::id)
+            .load(conn)
+            .unwrap()
+    }
+
+    #[test]
+    fn perform_skips_comments_on_closed_issues() {
+        let conn = &test_connection();
+        let open = test_issue(conn, 1, true, &[]);
+        let closed = test_issue(conn, 2, false, &[]);
+        let actions = [
+            Action::AddLabel(Label::FFCP),
+            Action::Comment("done"),
+            Action::EditComment(42, "edited"),
+            Action::Close,
+        ];
+
+        // comments are queued either way, whether they go out is decided when they're performed
+        enqueue(conn, &open, &actions).unwrap();
+        enqueue(conn, &closed, &actions).unwrap();
+
+        let skipped = |issue| {
+            actions_for(conn, issue)
+                .iter()
+                .map(|a| comments_on_closed_issue(issue, a))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(skipped(&open), vec![false, false, false, false]);
+        assert_eq!(skipped(&closed), vec![false, true, true, false]);
+    }
+
+    #[test]
+    fn drain_blocks_an_issue_on_failure() {
+        let conn = &test_connection();
+        let first = test_issue(conn, 1, true, &[]);
+        let second = test_issue(conn, 2, true, &[]);
+        enqueue(conn, &first, &[Action::Comment("a"), Action::Comment("b")]).unwrap();
+        enqueue(conn, &second, &[Action::Comment("c")]).unwrap();
+
+        // "a" fails, so "b" has to wait for it, but "c" doesn't
+        let mut performed = Vec::new();
+        drain_with(conn, |_, action| {
+            let body = action.body.clone().unwrap();
+            if body == "a" {
+                throw!(DashError::Misc(None));
+            }
+            performed.push(body);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(performed, vec!["c"]);
+
+        let pending = actions_for(conn, &first);
+        assert!(pending.iter().all(|a| a.completed_at.is_none()));
+        assert_eq!(pending[0].attempts, 1);
+        assert!(pending[0].last_error.is_some());
+        assert_eq!(pending[1].attempts, 0);
+
+        // once "a" succeeds, "b" follows it
+        let mut performed = Vec::new();
+        drain_with(conn, |_, action| {
+            performed.push(action.body.clone().unwrap());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(performed, vec!["a", "b"]);
+        assert!(actions_for(conn, &first)
+            .iter()
+            .all(|a| a.completed_at.is_some()));
+    }
+
+    #[test]
+    fn drain_gives_up_after_max_attempts() {
+        let conn = &test_connection();
+        let issue = test_issue(conn, 1, true, &[]);
+        enqueue(conn, &issue, &[Action::Close]).unwrap();
+
+        let mut tries = 0;
+        for _ in 0..MAX_ATTEMPTS + 2 {
+            drain_with(conn, |_, _| {
+                tries += 1;
+                throw!(DashError::Misc(None))
+            })
+            .unwrap();
+        }
+
+        assert_eq!(tries, MAX_ATTEMPTS);
+        let action = &actions_for(conn, &issue)[0];
+        assert_eq!(action.attempts, MAX_ATTEMPTS);
+        assert!(action.completed_at.is_none());
+    }
+}
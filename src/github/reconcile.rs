@@ -0,0 +1,418 @@
+//! Periodically compare the labels on issues with FCPs against what the FCP state says they should
+//! be, and fix any that have drifted (e.g. because a label change failed).
+
+use std::collections::BTreeSet;
+use std::sync::RwLock;
+use std::thread::JoinHandle;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+
+use super::command::{FcpDisposition, Label};
+use super::nag::{can_ffcp_close, can_ffcp_postpone};
+use super::outbox::{self, Action};
+use crate::config::CONFIG;
+use crate::domain::github::Issue;
+use crate::domain::rfcbot::FcpProposal;
+use crate::domain::schema::{fcp_proposal, issue};
+use crate::error::DashResult;
+use crate::DB_POOL;
+
+const DISPOSITIONS: [FcpDisposition; 3] = [
+    FcpDisposition::Merge,
+    FcpDisposition::Close,
+    FcpDisposition::Postpone,
+];
+
+/// The difference between an issue's labels and the ones its FCP state calls for.
+#[derive(Clone, Debug, Serialize)]
+pub struct LabelDiff {
+    pub repository: String,
+    pub number: i32,
+    pub add: Vec<&'static str>,
+    pub remove: Vec<&'static str>,
+}
+
+/// What the last run of the reconciler found.
+#[derive(Clone, Debug, Serialize)]
+pub struct LabelReport {
+    pub checked_at: NaiveDateTime,
+    pub dry_run: bool,
+    pub diffs: Vec<LabelDiff>,
+}
+
+lazy_static! {
+    static ref LAST_REPORT: RwLock<Option<LabelReport>> = RwLock::new(None);
+}
+
+/// Labels which must be present on or absent from an issue. Any other label is left alone.
+#[derive(Default)]
+struct ExpectedLabels {
+    present: Vec<Label>,
+    absent: Vec<Label>,
+}
+
+/// What an issue's repository does with the labels of finished FCPs.
+#[derive(Clone, Copy, Default)]
+struct RepoBehavior {
+    /// Finished FCPs to close are closed.
+    close: bool,
+    /// Finished FCPs to postpone are postponed.
+    postpone: bool,
+}
+
+impl RepoBehavior {
+    fn of(issue: &Issue) -> Self {
+        RepoBehavior {
+            close: can_ffcp_close(issue),
+            postpone: can_ffcp_postpone(issue),
+        }
+    }
+}
+
+pub fn start_reconciler_thread() -> Option<JoinHandle<()>> {
+    Some(crate::utils::spawn_thread(
+        "label reconciler",
+        CONFIG.label_reconcile_interval_mins?,
+        || {
+            let checked_at = Utc::now().naive_utc();
+            let dry_run = CONFIG.label_reconcile_dry_run;
+            let diffs = reconcile_labels(dry_run)?;
+            info!(
+                "label reconciler found {} issues with drifted labels",
+                diffs.len()
+            );
+            *LAST_REPORT.write().unwrap() = Some(LabelReport {
+                checked_at,
+                dry_run,
+                diffs,
+            });
+            Ok(())
+        },
+    ))
+}
+
+/// The report of the last run of the reconciler, if it has run.
+pub fn last_report() -> Option<LabelReport> { LAST_REPORT.read().unwrap().clone() }
+
+/// Work out which issues have labels that don't match their FCP state. Unless `dry_run` is set,
+/// fix them on GitHub as well.
+pub fn reconcile_labels(dry_run: bool) -> DashResult<Vec<LabelDiff>> {
+    let conn = &*DB_POOL.get()?;
+    let mut diffs = Vec::new();
+    let mut with_proposal = BTreeSet::new();
+
+    let proposals = fcp_proposal::table
+        .inner_join(issue::table)
+        .load::<(FcpProposal, Issue)>(conn)?;
+
+    for (proposal, issue) in proposals {
+        with_proposal.insert(issue.id);
+
+        // once a finished FCP's issue is closed, its labels are up to the humans
+        if proposal.fcp_closed && !issue.open {
+            continue;
+        }
+
+        let expected = ok_or_continue!(expected_labels(&proposal, RepoBehavior::of(&issue)), why =>
+            error!("Unable to work out labels for proposal {}: {:?}", proposal.id, why));
+        if let Some(diff) = reconcile_issue(conn, &issue, &expected, dry_run) {
+            diffs.push(diff);
+        }
+    }
+
+    // open issues which still claim to be in (proposed) FCP without a proposal, e.g. because
+    // removing the labels failed when the proposal was cancelled
+    let stale = issue::table
+        .filter(issue::open.eq(true))
+        .filter(issue::labels.overlaps_with(vec![
+            Label::PFCP.as_str().to_string(),
+            Label::FCP.as_str().to_string(),
+        ]))
+        .load::<Issue>(conn)?;
+
+    let expected = ExpectedLabels {
+        present: vec![],
+        absent: vec![Label::PFCP, Label::FCP],
+    };
+    for issue in stale {
+        if with_proposal.contains(&issue.id) {
+            continue;
+        }
+        if let Some(diff) = reconcile_issue(conn, &issue, &expected, dry_run) {
+            diffs.push(diff);
+        }
+    }
+
+    Ok(diffs)
+}
+
+/// The labels called for by a proposal's state, the same ones `nag` adds and removes as the
+/// proposal goes through it. Once the FCP has finished, `to-announce` and `S-waiting-on-review`
+/// are up to the humans, who remove them when they're done with them.
+fn expected_labels(proposal: &FcpProposal, behavior: RepoBehavior) -> DashResult<ExpectedLabels> {
+    let disposition = FcpDisposition::from_str(&proposal.disposition)?;
+    let mut expected = ExpectedLabels::default();
+
+    // only the current disposition's label may be present
+    expected.absent.extend(
+        DISPOSITIONS
+            .iter()
+            .filter(|&&d| d != disposition)
+            .map(|d| d.label()),
+    );
+
+    if proposal.fcp_closed {
+        expected.present.push(Label::FFCP);
+        expected
+            .absent
+            .extend(&[Label::PFCP, Label::FCP, Label::WaitingOnFCP]);
+
+        // auto-closed and auto-postponed issues swap their disposition label for the outcome
+        match disposition {
+            FcpDisposition::Close if behavior.close => {
+                expected.present.push(Label::Closed);
+                expected.absent.push(disposition.label());
+            }
+            FcpDisposition::Postpone if behavior.postpone => {
+                expected.present.push(Label::Postponed);
+                expected.absent.push(disposition.label());
+            }
+            _ => expected.present.push(disposition.label()),
+        }
+    } else if proposal.fcp_start.is_some() {
+        expected.present.extend(&[Label::FCP, disposition.label()]);
+        expected
+            .absent
+            .extend(&[Label::PFCP, Label::FFCP, Label::NeedsFCP]);
+    } else {
+        expected.present.extend(&[Label::PFCP, disposition.label()]);
+        expected
+            .absent
+            .extend(&[Label::FCP, Label::FFCP, Label::NeedsFCP]);
+    }
+
+    Ok(expected)
+}
+
+/// Compare an issue's labels against the expected ones, fixing them unless `dry_run` is set.
+///
+/// Issues with actions still waiting in the outbox are left for the next run, since their labels
+/// are about to change.
+fn reconcile_issue(
+    conn: &PgConnection,
+    issue: &Issue,
+    expected: &ExpectedLabels,
+    dry_run: bool,
+) -> Option<LabelDiff> {
+    let pending = ok_or!(outbox::has_pending(conn, issue), why => {
+        error!("Unable to check the outbox for {}#{}: {:?}", issue.repository, issue.number, why);
+        return None;
+    });
+    if pending {
+        return None;
+    }
+
+    let has = |label: &Label| issue.labels.iter().any(|l| l == label.as_str());
+    let add = expected
+        .present
+        .iter()
+        .filter(|l| !has(l))
+        .copied()
+        .collect::<Vec<_>>();
+    let remove = expected
+        .absent
+        .iter()
+        .filter(|l| has(l))
+        .copied()
+        .collect::<Vec<_>>();
+
+    if add.is_empty() && remove.is_empty() {
+        return None;
+    }
+
+    let diff = LabelDiff {
+        repository: issue.repository.clone(),
+        number: issue.number,
+        add: add.iter().map(|l| l.as_str()).collect(),
+        remove: remove.iter().map(|l| l.as_str()).collect(),
+    };
+
+    info!(
+        "labels on {}#{} have drifted, adding {:?} and removing {:?}{}",
+        diff.repository,
+        diff.number,
+        diff.add,
+        diff.remove,
+        if dry_run { " (dry run)" } else { "" }
+    );
+
+    if !dry_run {
+        ok_or!(fix_labels(conn, issue, &add, &remove), why =>
+            error!("Unable to fix labels on {}#{}: {:?}", diff.repository, diff.number, why));
+    }
+
+    Some(diff)
+}
+
+/// Queue the label changes in the outbox, which also respects `POST_COMMENTS`. The issue's labels
+/// are updated by the webhooks once GitHub has made the changes.
+fn fix_labels(
+    conn: &PgConnection,
+    issue: &Issue,
+    add: &[Label],
+    remove: &[Label],
+) -> DashResult<()> {
+    let actions = add
+        .iter()
+        .map(|&label| Action::AddLabel(label))
+        .chain(remove.iter().map(|&label| Action::RemoveLabel(label)))
+        .collect::<Vec<_>>();
+
+    outbox::enqueue(conn, issue, &actions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::github::tests::{test_connection, test_issue};
+
+    fn proposal(disposition: &str, started: bool, closed: bool) -> FcpProposal {
+        FcpProposal {
+            id: 1,
+            fk_issue: 1,
+            fk_initiator: 1,
+            fk_initiating_comment: 1,
+            disposition: disposition.to_string(),
+            fk_bot_tracking_comment: 2,
+            fcp_start: if started {
+                Some(Utc::now().naive_utc())
+            } else {
+                None
+            },
+            fcp_closed: closed,
+            extended_end: None,
+            held_at: None,
+            hold_reason: None,
+            fk_held_by: None,
+            fcp_remaining_secs: None,
+        }
+    }
+
+    fn expected(
+        proposal: &FcpProposal,
+        behavior: RepoBehavior,
+    ) -> (Vec<&'static str>, Vec<&'static str>) {
+        let expected = expected_labels(proposal, behavior).unwrap();
+        let names = |labels: &[Label]| {
+            let mut names = labels.iter().map(|l| l.as_str()).collect::<Vec<_>>();
+            names.sort_unstable();
+            names
+        };
+        (names(&expected.present), names(&expected.absent))
+    }
+
+    #[test]
+    fn expected_labels_pending() {
+        let (present, absent) = expected(&proposal("merge", false, false), RepoBehavior::default());
+        assert_eq!(
+            present,
+            vec!["disposition-merge", "proposed-final-comment-period"]
+        );
+        assert_eq!(
+            absent,
+            vec![
+                "disposition-close",
+                "disposition-postpone",
+                "final-comment-period",
+                "finished-final-comment-period",
+                "needs-fcp",
+            ]
+        );
+    }
+
+    #[test]
+    fn expected_labels_running() {
+        let (present, absent) =
+            expected(&proposal("postpone", true, false), RepoBehavior::default());
+        assert_eq!(
+            present,
+            vec!["disposition-postpone", "final-comment-period"]
+        );
+        assert_eq!(
+            absent,
+            vec![
+                "disposition-close",
+                "disposition-merge",
+                "finished-final-comment-period",
+                "needs-fcp",
+                "proposed-final-comment-period",
+            ]
+        );
+    }
+
+    #[test]
+    fn expected_labels_finished() {
+        let finished = proposal("merge", true, true);
+
+        let (present, absent) = expected(&finished, RepoBehavior::default());
+        // to-announce and S-waiting-on-review are left to the humans
+        assert_eq!(
+            present,
+            vec!["disposition-merge", "finished-final-comment-period"]
+        );
+        assert_eq!(
+            absent,
+            vec![
+                "S-waiting-on-fcp",
+                "disposition-close",
+                "disposition-postpone",
+                "final-comment-period",
+                "proposed-final-comment-period",
+            ]
+        );
+    }
+
+    #[test]
+    fn expected_labels_finished_auto_close() {
+        let finished = proposal("close", true, true);
+
+        let (present, absent) = expected(&finished, RepoBehavior::default());
+        assert!(present.contains(&"disposition-close"));
+        assert!(!present.contains(&"closed"));
+        assert!(!absent.contains(&"disposition-close"));
+
+        let behavior = RepoBehavior {
+            close: true,
+            ..RepoBehavior::default()
+        };
+        let (present, absent) = expected(&finished, behavior);
+        assert!(present.contains(&"closed"));
+        assert!(absent.contains(&"disposition-close"));
+    }
+
+    #[test]
+    fn reconcile_issue_waits_for_the_outbox() {
+        let conn = &test_connection();
+        let issue = test_issue(conn, 1, true, &["final-comment-period"]);
+        let expected = ExpectedLabels {
+            present: vec![],
+            absent: vec![Label::FCP],
+        };
+
+        let diff = reconcile_issue(conn, &issue, &expected, false).unwrap();
+        assert_eq!(diff.remove, vec!["final-comment-period"]);
+
+        // the labels only change once GitHub tells us, and until then the fix isn't queued again
+        let stored = issue::table.find(issue.id).first::<Issue>(conn).unwrap();
+        assert_eq!(stored.labels, vec!["final-comment-period"]);
+        assert!(reconcile_issue(conn, &stored, &expected, false).is_none());
+    }
+
+    #[test]
+    fn expected_labels_unknown_disposition() {
+        let bogus = proposal("explode", false, false);
+        assert!(expected_labels(&bogus, RepoBehavior::default()).is_err());
+    }
+}
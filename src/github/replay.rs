@@ -0,0 +1,452 @@
+//! Rebuild the FCP and poll state of an issue or a whole repository from its stored comments, to
+//! recover from bugs and from manual edits to the database.
+//!
+//! Replaying clears out the existing proposals (along with their concerns and review requests) and
+//! polls, then processes each comment in the order it was made, evaluating the state as of that
+//! comment's creation. Nothing is sent to GitHub: comments the bot would post are matched up with
+//! the ones it posted the first time round, and label changes and edits are skipped.
+//!
+//! Rebuilt proposals keep their ids, so that their events still belong to them. Once the comments
+//! have been replayed, the state is evaluated as of now, and anything that has happened since the
+//! last comment, like an FCP finishing, is announced on GitHub as usual.
+//!
+//! An issue is replayed in a single transaction, so if rebuilding its state fails, it keeps the
+//! state it had before.
+//!
+//! Ticked boxes in status comments can't be dated, so reviews made that way count from when the
+//! status comment was posted. The diff shows where that moves the start of an FCP.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+
+use super::nag::{self, Context, NAG_LOCK};
+use crate::config::RFC_BOT_MENTIONS;
+use crate::domain::github::{GitHubUser, Issue, IssueComment};
+use crate::domain::rfcbot::{FcpConcern, FcpProposal, FcpReviewRequest, Poll, PollResponseRequest};
+use crate::domain::schema::{
+    fcp_concern, fcp_proposal, fcp_review_request, githubuser, issue, issuecomment, poll,
+    poll_response_request,
+};
+use crate::error::{DashError, DashResult};
+use crate::DB_POOL;
+
+/// Find the comment the bot posted when this comment would have been posted the first time round:
+/// the first comment from the bot since the replayed comment which starts the same way.
+pub(super) fn recorded_bot_comment(
+    ctx: &Context<'_>,
+    issue: &Issue,
+    body: &str,
+) -> DashResult<IssueComment> {
+    use diesel::pg::expression::dsl::any;
+    let conn = ctx.conn;
+
+    let bot_logins = RFC_BOT_MENTIONS
+        .iter()
+        .map(|mention| mention.trim_start_matches('@'))
+        .collect::<Vec<_>>();
+
+    let candidates = issuecomment::table
+        .inner_join(githubuser::table)
+        .filter(issuecomment::fk_issue.eq(issue.id))
+        .filter(issuecomment::created_at.ge(ctx.now()))
+        .filter(githubuser::login.eq(any(bot_logins)))
+        .order((issuecomment::created_at, issuecomment::id))
+        .select(issuecomment::all_columns)
+        .load::<IssueComment>(conn)?;
+
+    let first_line = body.lines().next().unwrap_or("");
+    match matching_comment(candidates, first_line) {
+        Some(comment) => Ok(comment),
+        None => throw!(DashError::Misc(Some(format!(
+            "no comment from the bot on {}#{} after {} starting with {:?}",
+            issue.repository,
+            issue.number,
+            ctx.now(),
+            first_line
+        )))),
+    }
+}
+
+/// The first of the comments whose first line is the given one.
+fn matching_comment(candidates: Vec<IssueComment>, first_line: &str) -> Option<IssueComment> {
+    candidates
+        .into_iter()
+        .find(|c| c.body.lines().next().unwrap_or("") == first_line)
+}
+
+/// Replay the comments on one issue of a repository, or on all of its issues, returning a diff
+/// between the old state and the rebuilt one.
+pub fn replay(repository: &str, number: Option<i32>) -> DashResult<Vec<String>> {
+    let conn = &*DB_POOL.get()?;
+
+    let mut query = issue::table
+        .filter(issue::repository.eq(repository))
+        .into_boxed();
+    if let Some(number) = number {
+        query = query.filter(issue::number.eq(number));
+    }
+    let issues = query.order(issue::number).load::<Issue>(conn)?;
+
+    if issues.is_empty() {
+        throw!(DashError::Misc(Some(format!(
+            "no issues found in {} to replay",
+            repository
+        ))));
+    }
+
+    let mut diff = Vec::new();
+    for issue in issues {
+        diff.extend(replay_issue(conn, &issue)?);
+    }
+    Ok(diff)
+}
+
+fn replay_issue(conn: &PgConnection, issue: &Issue) -> DashResult<Vec<String>> {
+    // keep webhooks and the evaluator out until the issue is rebuilt
+    let _in_progress_marker = NAG_LOCK.lock();
+
+    let before = describe_state(conn, issue)?;
+
+    let comments = issuecomment::table
+        .filter(issuecomment::fk_issue.eq(issue.id))
+        .order((issuecomment::created_at, issuecomment::id))
+        .load::<IssueComment>(conn)?;
+
+    info!(
+        "replaying {} comments on {}#{}",
+        comments.len(),
+        issue.repository,
+        issue.number
+    );
+
+    let after = conn.transaction::<_, DashError, _>(|| {
+        // rebuilt proposals keep their ids, so that their events still belong to them
+        let proposal_ids = fcp_proposal::table
+            .filter(fcp_proposal::fk_issue.eq(issue.id))
+            .select((fcp_proposal::fk_initiating_comment, fcp_proposal::id))
+            .load::<(i64, i32)>(conn)?
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        // concerns, review requests and poll responses go too: the schema has ON DELETE CASCADE
+        diesel::delete(fcp_proposal::table.filter(fcp_proposal::fk_issue.eq(issue.id)))
+            .execute(conn)?;
+        diesel::delete(poll::table.filter(poll::fk_issue.eq(issue.id))).execute(conn)?;
+
+        for comment in &comments {
+            let ctx = &Context::replay(conn, issue, comment.created_at, &proposal_ids);
+            // a comment which fails is rolled back on its own, like it would have been the first
+            // time round
+            let processed = conn.transaction(|| nag::process_comment(ctx, comment, None));
+            ok_or!(processed, why =>
+                warn!("Unable to replay comment {}: {:?}", comment.id, why));
+            nag::evaluate_nags(ctx);
+        }
+
+        describe_state(conn, issue)
+    })?;
+
+    // catch up on anything that has happened since, like an FCP finishing, which GitHub hasn't
+    // been told about yet
+    nag::evaluate_nags(&Context::new(conn));
+
+    Ok(before
+        .difference(&after)
+        .map(|line| format!("- {}", line))
+        .chain(after.difference(&before).map(|line| format!("+ {}", line)))
+        .collect())
+}
+
+/// Describe the FCP and poll state of an issue, one line per fact.
+fn describe_state(conn: &PgConnection, issue: &Issue) -> DashResult<BTreeSet<String>> {
+    let prefix = format!("{}#{}", issue.repository, issue.number);
+    let mut lines = BTreeSet::new();
+
+    let proposals = fcp_proposal::table
+        .inner_join(githubuser::table)
+        .filter(fcp_proposal::fk_issue.eq(issue.id))
+        .load::<(FcpProposal, GitHubUser)>(conn)?;
+
+    for (proposal, initiator) in proposals {
+        let status = if proposal.fcp_closed {
+            "FCP finished".to_string()
+        } else if let Some(start) = proposal.fcp_start {
+            let end = proposal
+                .extended_end
+                .map(|end| format!(", ending {}", end.format("%Y-%m-%d %H:%M")))
+                .unwrap_or_default();
+            format!("FCP started {}{}", start.format("%Y-%m-%d %H:%M"), end)
+        } else {
+            "pending".to_string()
+        };
+        let hold = proposal
+            .hold_reason
+            .as_ref()
+            .map(|reason| format!(", on hold: {}", reason))
+            .unwrap_or_default();
+        lines.insert(format!(
+            "{}: proposal to {} by @{}, {}{}",
+            prefix, proposal.disposition, initiator.login, status, hold
+        ));
+
+        let reviews = fcp_review_request::table
+            .inner_join(githubuser::table)
+            .filter(fcp_review_request::fk_proposal.eq(proposal.id))
+            .load::<(FcpReviewRequest, GitHubUser)>(conn)?;
+        for (review, reviewer) in reviews {
+            lines.insert(format!(
+                "{}: review by @{}{}{}",
+                prefix,
+                reviewer.login,
+                review
+                    .team_label
+                    .map(|team| format!(" for {}", team))
+                    .unwrap_or_default(),
+                if review.reviewed {
+                    ", done"
+                } else if review.abstained {
+                    ", abstained"
+                } else {
+                    ""
+                }
+            ));
+        }
+
+        let concerns = fcp_concern::table
+            .inner_join(githubuser::table)
+            .filter(fcp_concern::fk_proposal.eq(proposal.id))
+            .load::<(FcpConcern, GitHubUser)>(conn)?;
+        for (concern, initiator) in concerns {
+            lines.insert(format!(
+                "{}: concern {} raised by @{}{}",
+                prefix,
+                concern.name,
+                initiator.login,
+                if concern.fk_resolved_comment.is_some() {
+                    ", resolved"
+                } else {
+                    ""
+                }
+            ));
+        }
+    }
+
+    let polls = poll::table
+        .inner_join(githubuser::table)
+        .filter(poll::fk_issue.eq(issue.id))
+        .load::<(Poll, GitHubUser)>(conn)?;
+
+    for (survey, initiator) in polls {
+        lines.insert(format!(
+            "{}: poll by @{} on {:?}{}",
+            prefix,
+            initiator.login,
+            survey.poll_question,
+            if survey.poll_closed { ", closed" } else { "" }
+        ));
+
+        let responses = poll_response_request::table
+            .inner_join(githubuser::table)
+            .filter(poll_response_request::fk_poll.eq(survey.id))
+            .load::<(PollResponseRequest, GitHubUser)>(conn)?;
+        for (response, respondent) in responses {
+            lines.insert(format!(
+                "{}: poll response from @{}{}",
+                prefix,
+                respondent.login,
+                if response.responded { ", done" } else { "" }
+            ));
+        }
+    }
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::rfcbot::{
+        NewFcpConcern, NewFcpProposal, NewFcpReviewRequest, NewPoll, NewPollResponseRequest,
+    };
+    use crate::github::tests::{test_comment, test_connection, test_issue, test_user};
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+    fn time(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn closed_issue(id: i32, closed_at: NaiveDateTime) -> Issue {
+        Issue {
+            id,
+            number: id,
+            fk_milestone: None,
+            fk_user: 1,
+            fk_assignee: None,
+            open: false,
+            is_pull_request: false,
+            title: String::new(),
+            body: String::new(),
+            locked: false,
+            closed_at: Some(closed_at),
+            created_at: time(1, 0),
+            updated_at: closed_at,
+            labels: vec![],
+            repository: "rust-lang/test".to_string(),
+        }
+    }
+
+    #[test]
+    fn clock_and_scope() {
+        let conn = &test_connection();
+        let issue = closed_issue(1, time(10, 0));
+        let proposal_ids = BTreeMap::new();
+
+        let ctx = Context::new(conn);
+        assert!(!ctx.is_replaying());
+        assert!(ctx.in_scope(1) && ctx.in_scope(2));
+        assert!(ctx.now() > time(10, 0));
+        assert!(!ctx.is_open(&issue));
+
+        let ctx = Context::replay(conn, &issue, time(9, 0), &proposal_ids);
+        assert!(ctx.is_replaying());
+        assert!(ctx.in_scope(1));
+        assert!(!ctx.in_scope(2));
+
+        // the issue was open until it was closed
+        assert_eq!(ctx.now(), time(9, 0));
+        assert!(ctx.is_open(&issue));
+        let ctx = Context::replay(conn, &issue, time(11, 0), &proposal_ids);
+        assert_eq!(ctx.now(), time(11, 0));
+        assert!(!ctx.is_open(&issue));
+    }
+
+    #[test]
+    fn bot_comments_match_by_first_line() {
+        let comment = |id, body: &str| IssueComment {
+            id,
+            fk_issue: 1,
+            fk_user: 1,
+            body: body.to_string(),
+            created_at: time(1, 0),
+            updated_at: time(1, 0),
+            repository: "rust-lang/test".to_string(),
+        };
+        let candidates = || {
+            vec![
+                comment(1, "Team member @a has proposed to merge this.\n\n- [ ] @b"),
+                comment(
+                    2,
+                    ":bell: **This is now entering its final comment period**",
+                ),
+            ]
+        };
+
+        let found = matching_comment(
+            candidates(),
+            ":bell: **This is now entering its final comment period**",
+        );
+        assert_eq!(found.map(|c| c.id), Some(2));
+
+        // an unrelated comment is no substitute
+        assert_eq!(
+            matching_comment(candidates(), "@a proposal cancelled."),
+            None
+        );
+    }
+
+    #[test]
+    fn describe_state_lists_everything() {
+        let conn = &test_connection();
+        let alice = test_user(conn, -101, "alice");
+        let bob = test_user(conn, -102, "bob");
+        let issue = test_issue(conn, 1, true, &[]);
+        let proposed = test_comment(conn, &issue, &alice, "@rfcbot merge");
+        let status = test_comment(conn, &issue, &alice, "Team member @alice has proposed");
+
+        let proposal = diesel::insert_into(fcp_proposal::table)
+            .values(&NewFcpProposal {
+                id: None,
+                fk_issue: issue.id,
+                fk_initiator: alice.id,
+                fk_initiating_comment: proposed.id,
+                disposition: "merge",
+                fk_bot_tracking_comment: status.id,
+                fcp_start: Some(time(1, 12)),
+                fcp_closed: false,
+            })
+            .get_result::<FcpProposal>(conn)
+            .unwrap();
+        diesel::update(fcp_proposal::table.find(proposal.id))
+            .set(fcp_proposal::extended_end.eq(time(1, 12) + Duration::days(20)))
+            .execute(conn)
+            .unwrap();
+        diesel::insert_into(fcp_review_request::table)
+            .values(&vec![
+                NewFcpReviewRequest {
+                    fk_proposal: proposal.id,
+                    fk_reviewer: alice.id,
+                    reviewed: true,
+                    team_label: Some("T-lang"),
+                },
+                NewFcpReviewRequest {
+                    fk_proposal: proposal.id,
+                    fk_reviewer: bob.id,
+                    reviewed: false,
+                    team_label: None,
+                },
+            ])
+            .execute(conn)
+            .unwrap();
+        diesel::insert_into(fcp_concern::table)
+            .values(&NewFcpConcern {
+                fk_proposal: proposal.id,
+                fk_initiator: bob.id,
+                fk_resolved_comment: Some(status.id),
+                name: "naming",
+                fk_initiating_comment: proposed.id,
+            })
+            .execute(conn)
+            .unwrap();
+
+        let survey = diesel::insert_into(poll::table)
+            .values(&NewPoll {
+                fk_issue: issue.id,
+                fk_initiator: bob.id,
+                fk_initiating_comment: proposed.id,
+                fk_bot_tracking_comment: status.id,
+                poll_question: "Which colour?",
+                poll_created_at: time(2, 0),
+                poll_closed: true,
+                poll_teams: "T-lang",
+            })
+            .get_result::<Poll>(conn)
+            .unwrap();
+        diesel::insert_into(poll_response_request::table)
+            .values(&NewPollResponseRequest {
+                fk_poll: survey.id,
+                fk_respondent: alice.id,
+                responded: false,
+            })
+            .execute(conn)
+            .unwrap();
+
+        let lines = describe_state(conn, &issue).unwrap();
+        assert_eq!(
+            lines.into_iter().collect::<Vec<_>>(),
+            vec![
+                "rust-lang/test#1: concern naming raised by @bob, resolved",
+                "rust-lang/test#1: poll by @bob on \"Which colour?\", closed",
+                "rust-lang/test#1: poll response from @alice",
+                "rust-lang/test#1: proposal to merge by @alice, FCP started 2026-10-01 12:00, \
+                 ending 2026-10-21 12:00",
+                "rust-lang/test#1: review by @alice for T-lang, done",
+                "rust-lang/test#1: review by @bob",
+            ]
+        );
+    }
+}
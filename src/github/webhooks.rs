@@ -8,8 +8,9 @@ use rocket::outcome::Outcome::*;
 use rocket::request::Request;
 
 use crate::config::CONFIG;
+use crate::domain::github::GitHubUser;
 use crate::error::{DashError, DashResult};
-use crate::github::models::{CommentFromJson, IssueFromJson, PullRequestFromJson};
+use crate::github::models::{CommentFromJson, IssueFromJson, LabelFromJson, PullRequestFromJson};
 
 #[derive(Debug)]
 pub struct Event {
@@ -144,6 +145,8 @@ pub struct IssuesEvent {
     pub action: String,
     pub issue: IssueFromJson,
     pub repository: Repository,
+    /// The label added or removed, for `labeled` and `unlabeled` events.
+    pub label: Option<LabelFromJson>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -152,6 +155,8 @@ pub struct IssueCommentEvent {
     pub issue: IssueFromJson,
     pub repository: Repository,
     pub comment: CommentFromJson,
+    /// Who created, edited or deleted the comment.
+    pub sender: GitHubUser,
 }
 
 #[derive(Debug, Deserialize)]
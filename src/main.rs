@@ -70,7 +70,17 @@ fn main() {
         info!("parsed teams: {:?}", parsed_teams);
     }
 
+    // `rfcbot-rs replay <owner/repo> [<issue number>]` rebuilds FCP state instead of serving
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().map(String::as_str) == Some("replay") {
+        replay(&args[1..]);
+        return;
+    }
+
     teams::start_updater_thread();
+    github::start_evaluator_thread();
+    github::outbox::start_outbox_thread();
+    let _ = github::reconcile::start_reconciler_thread();
 
     // FIXME(anp) need to handle panics in both the listeners and crash the server
     let _ = scraper::start_scraping();
@@ -80,6 +90,38 @@ fn main() {
     //server_handle.join().expect("problem running server!").expect("problem while running server");
 }
 
+fn replay(args: &[String]) {
+    let (repository, number) = match args {
+        [repository] => (repository, None),
+        [repository, number] => match number.parse() {
+            Ok(number) => (repository, Some(number)),
+            Err(_) => {
+                eprintln!("invalid issue number: {}", number);
+                std::process::exit(1);
+            }
+        },
+        _ => {
+            eprintln!("usage: rfcbot-rs replay <owner/repo> [<issue number>]");
+            std::process::exit(1);
+        }
+    };
+
+    match github::replay::replay(repository, number) {
+        Ok(diff) => {
+            if diff.is_empty() {
+                println!("replayed state matches the current state");
+            }
+            for line in diff {
+                println!("{}", line);
+            }
+        }
+        Err(why) => {
+            error!("Unable to replay comments: {:?}", why);
+            std::process::exit(1);
+        }
+    }
+}
+
 // initialize the database connection pool
 lazy_static! {
     pub static ref DB_POOL: Pool<ConnectionManager<PgConnection>> = {
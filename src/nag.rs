@@ -1,17 +1,25 @@
+use std::collections::BTreeSet;
+
+use diesel::pg::PgConnection;
 use diesel::prelude::*;
 
 use crate::domain::github::{GitHubUser, Issue, IssueComment};
-use crate::domain::rfcbot::{FcpConcern, FcpProposal, FcpReviewRequest};
+use crate::domain::rfcbot::{FcpConcern, FcpEvent, FcpProposal, FcpReviewRequest};
 use crate::domain::schema::fcp_concern;
 use crate::error::DashResult;
+use crate::teams::SETUP;
 use crate::DB_POOL;
 
 #[derive(Serialize)]
 pub struct FcpWithInfo {
     pub fcp: FcpProposal,
     pub reviews: Vec<(GitHubUser, bool)>,
-    // (Concern name, comment registering it, and user leaving it)
-    pub concerns: Vec<(String, IssueComment, GitHubUser)>,
+    // (User abstaining, and why)
+    pub abstentions: Vec<(GitHubUser, Option<String>)>,
+    // (Concern name, comment registering it, user leaving it, and whether they've left every team)
+    pub concerns: Vec<(String, IssueComment, GitHubUser, bool)>,
+    // (User who put it on hold, and why)
+    pub hold: Option<(GitHubUser, String)>,
     pub issue: Issue,
     pub status_comment: IssueComment,
 }
@@ -23,10 +31,22 @@ pub fn all_fcps() -> DashResult<Vec<FcpWithInfo>> {
 
     let conn = &*DB_POOL.get()?;
 
+    // FCPs which are on hold aren't going anywhere either
     let proposals = fcp_proposal::table
-        .filter(fcp_proposal::fcp_start.is_null())
+        .filter(
+            fcp_proposal::fcp_start
+                .is_null()
+                .or(fcp_proposal::held_at.is_not_null()),
+        )
         .load::<FcpProposal>(conn)?;
 
+    let team_members = SETUP
+        .read()
+        .unwrap()
+        .teams()
+        .flat_map(|(_, team)| team.member_logins().map(str::to_string))
+        .collect::<BTreeSet<_>>();
+
     let mut all_fcps = Vec::new();
 
     for fcp in proposals {
@@ -47,22 +67,47 @@ pub fn all_fcps() -> DashResult<Vec<FcpWithInfo>> {
             }
             let user = githubuser::table
                 .filter(githubuser::id.eq(concern.fk_initiator))
-                .first(conn)?;
+                .first::<GitHubUser>(conn)?;
             let comment = issuecomment::table
                 .filter(issuecomment::id.eq(concern.fk_initiating_comment))
                 .first::<IssueComment>(conn)?;
-            concerns.push((concern.name, comment, user));
+            // nobody can resolve these without `resolve-for`
+            let departed = !team_members.contains(&user.login);
+            concerns.push((concern.name, comment, user, departed));
         }
 
-        let mut reviews_with_users = Vec::new();
+        let mut reviews_with_users: Vec<(GitHubUser, bool)> = Vec::new();
+        let mut abstentions: Vec<(GitHubUser, Option<String>)> = Vec::new();
 
         for review in reviews {
+            // members of several teams have one review request per team
+            let seen = |user: &GitHubUser| user.id == review.fk_reviewer;
+            if reviews_with_users.iter().any(|(user, _)| seen(user))
+                || abstentions.iter().any(|(user, _)| seen(user))
+            {
+                continue;
+            }
+
             let user = githubuser::table
                 .filter(githubuser::id.eq(review.fk_reviewer))
                 .first(conn)?;
-            reviews_with_users.push((user, review.reviewed));
+            if review.abstained {
+                abstentions.push((user, review.abstain_reason));
+            } else {
+                reviews_with_users.push((user, review.reviewed));
+            }
         }
 
+        let hold = match (fcp.fk_held_by, &fcp.hold_reason) {
+            (Some(holder), Some(reason)) => Some((
+                githubuser::table
+                    .filter(githubuser::id.eq(holder))
+                    .first(conn)?,
+                reason.clone(),
+            )),
+            _ => None,
+        };
+
         let status_comment = issuecomment::table
             .filter(issuecomment::id.eq(fcp.fk_bot_tracking_comment))
             .first::<IssueComment>(conn)?;
@@ -74,7 +119,9 @@ pub fn all_fcps() -> DashResult<Vec<FcpWithInfo>> {
         let fcp_with_info = FcpWithInfo {
             fcp,
             reviews: reviews_with_users,
+            abstentions,
             concerns,
+            hold,
             issue,
             status_comment,
         };
@@ -108,10 +155,17 @@ pub fn individual_nags(username: &str) -> DashResult<(GitHubUser, Vec<Individual
         .filter(fcp_proposal::fcp_closed.eq(false))
         .filter(fcp_review_request::fk_reviewer.eq(user.id))
         .filter(fcp_review_request::reviewed.eq(false))
+        .filter(fcp_review_request::abstained.eq(false))
         .load::<(FcpReviewRequest, FcpProposal)>(conn)?;
 
     let mut fcps = Vec::new();
+    let mut seen_proposals = BTreeSet::new();
     for (rr, proposal) in review_requests {
+        // members of several teams have one review request per team
+        if !seen_proposals.insert(proposal.id) {
+            continue;
+        }
+
         let issue = issue::table
             .filter(issue::id.eq(proposal.fk_issue))
             .first::<Issue>(conn)?;
@@ -119,8 +173,18 @@ pub fn individual_nags(username: &str) -> DashResult<(GitHubUser, Vec<Individual
         let reviews = fcp_review_request::table
             .filter(fcp_review_request::fk_proposal.eq(proposal.id))
             .load::<FcpReviewRequest>(conn)?;
-        let reviews_given = reviews.iter().filter(|r| r.reviewed).count() as i32;
-        let reviews_requested = reviews.len() as i32;
+        let reviewers = reviews
+            .iter()
+            .filter(|r| !r.abstained)
+            .map(|r| r.fk_reviewer)
+            .collect::<BTreeSet<_>>();
+        let reviewers_done = reviews
+            .iter()
+            .filter(|r| r.reviewed)
+            .map(|r| r.fk_reviewer)
+            .collect::<BTreeSet<_>>();
+        let reviews_given = reviewers_done.len() as i32;
+        let reviews_requested = reviewers.len() as i32;
 
         let unresolved_concerns = fcp_concern::table
             .filter(fcp_concern::fk_proposal.eq(proposal.id))
@@ -140,3 +204,33 @@ pub fn individual_nags(username: &str) -> DashResult<(GitHubUser, Vec<Individual
 
     Ok((user, fcps))
 }
+
+#[derive(Serialize)]
+pub struct FcpEventWithActor {
+    pub event: FcpEvent,
+    pub actor: Option<GitHubUser>,
+}
+
+/// List everything that has happened to a proposal, oldest first. This works for cancelled
+/// proposals too, which no longer exist.
+pub fn fcp_events(proposal_id: i32) -> DashResult<Vec<FcpEventWithActor>> {
+    load_fcp_events(&*DB_POOL.get()?, proposal_id)
+}
+
+pub(crate) fn load_fcp_events(
+    conn: &PgConnection,
+    proposal_id: i32,
+) -> DashResult<Vec<FcpEventWithActor>> {
+    use crate::domain::schema::{fcp_event, githubuser};
+
+    let events = fcp_event::table
+        .left_join(githubuser::table)
+        .filter(fcp_event::fk_proposal.eq(proposal_id))
+        .order(fcp_event::id)
+        .load::<(FcpEvent, Option<GitHubUser>)>(conn)?;
+
+    Ok(events
+        .into_iter()
+        .map(|(event, actor)| FcpEventWithActor { event, actor })
+        .collect())
+}
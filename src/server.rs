@@ -12,7 +12,13 @@ pub fn serve() {
             rocket::ignite()
                 .mount(
                     "/api",
-                    routes![api::all_fcps, api::member_fcps, api::github_webhook],
+                    routes![
+                        api::all_fcps,
+                        api::member_fcps,
+                        api::fcp_events,
+                        api::label_report,
+                        api::github_webhook
+                    ],
                 )
                 .mount("/", routes![html::all_fcps, html::member_fcps])
                 .register(catchers![not_found])
@@ -45,7 +51,9 @@ mod html {
             let nag::FcpWithInfo {
                 fcp,
                 reviews,
+                abstentions,
                 mut concerns,
+                hold,
                 issue,
                 status_comment,
             } = fcp;
@@ -58,8 +66,15 @@ mod html {
 
             pending_reviewers.sort();
 
+            let abstentions = abstentions
+                .into_iter()
+                .map(|(user, reason)| json!({ "login": user.login, "reason": reason }))
+                .collect::<Vec<_>>();
+
             concerns.sort_by_key(|c| c.0.clone());
 
+            let hold = hold.map(|(user, reason)| json!({ "login": user.login, "reason": reason }));
+
             let record = json!({
                 "disposition": fcp.disposition,
                 "issue": issue,
@@ -67,11 +82,14 @@ mod html {
                     "id": status_comment.id as u32
                 },
                 "pendingReviewers": pending_reviewers,
+                "abstentions": abstentions,
+                "hold": hold,
                 "pendingConcerns": concerns.into_iter().map(|c| {
                     json!({
                         "name": c.0.clone(),
                         "commentId": c.1.id as u32,
                         "login": c.2.login.clone(),
+                        "departed": c.3,
                     })
                 }).collect::<Vec<_>>(),
             });
@@ -117,8 +135,11 @@ mod html {
 mod api {
     use crate::domain::github::GitHubUser;
     use crate::error::DashResult;
+    use crate::github::reconcile::{self, LabelReport};
     use crate::github::webhooks::{Event, Payload};
-    use crate::github::{handle_comment, handle_issue, handle_pr};
+    use crate::github::{
+        handle_comment, handle_deleted_comment, handle_issue, handle_label, handle_pr,
+    };
     use crate::nag;
     use crate::DB_POOL;
     use rocket_contrib::json::Json;
@@ -133,6 +154,17 @@ mod api {
         Ok(Json(nag::individual_nags(&username)?))
     }
 
+    /// Everything that has happened to a proposal, including cancelled ones.
+    #[get("/fcp/<id>/events")]
+    pub fn fcp_events(id: i32) -> DashResult<Json<Vec<nag::FcpEventWithActor>>> {
+        Ok(Json(nag::fcp_events(id)?))
+    }
+
+    /// The issues whose labels didn't match their FCP state when the label reconciler last ran.
+    /// Not found if it hasn't run.
+    #[get("/labels/report")]
+    pub fn label_report() -> Option<Json<LabelReport>> { reconcile::last_report().map(Json) }
+
     #[post("/github-webhook", data = "<event>")]
     pub fn github_webhook(event: Event) -> DashResult<()> {
         let conn = &*DB_POOL.get()?;
@@ -149,7 +181,17 @@ mod api {
 
         match event.payload {
             Payload::Issues(issue_event) => {
-                handle_issue(conn, issue_event.issue, &issue_event.repository.full_name)?;
+                let repo = &issue_event.repository.full_name;
+                let number = issue_event.issue.number;
+                handle_issue(conn, issue_event.issue, repo)?;
+
+                if let Some(label) = issue_event.label {
+                    match &*issue_event.action {
+                        "labeled" => handle_label(conn, repo, number, &label.name, true)?,
+                        "unlabeled" => handle_label(conn, repo, number, &label.name, false)?,
+                        _ => {}
+                    }
+                }
             }
 
             Payload::PullRequest(pr_event) => {
@@ -157,11 +199,11 @@ mod api {
             }
 
             Payload::IssueComment(comment_event) => {
-                // possible race conditions if we get a comment hook before the issue one (or we
-                // missed the issue one), so make sure the issue exists first
-
-                if comment_event.action != "deleted" {
-                    // TODO handle deleted comments properly
+                if comment_event.action == "deleted" {
+                    handle_deleted_comment(conn, comment_event.comment)?;
+                } else {
+                    // possible race conditions if we get a comment hook before the issue one (or
+                    // we missed the issue one), so make sure the issue exists first
                     handle_issue(
                         conn,
                         comment_event.issue,
@@ -171,6 +213,7 @@ mod api {
                         conn,
                         comment_event.comment,
                         &comment_event.repository.full_name,
+                        Some(&comment_event.sender),
                     )?;
                 }
             }
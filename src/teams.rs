@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use std::sync::{Arc, RwLock};
 
+use chrono::Duration;
 use diesel::prelude::*;
 
 use super::DB_POOL;
@@ -10,6 +11,8 @@ use crate::github::GH;
 
 const UPDATE_CONFIG_EVERY_MIN: u64 = 5;
 
+const DEFAULT_FCP_DURATION_DAYS: i64 = 10;
+
 //==============================================================================
 // Public API
 //==============================================================================
@@ -24,6 +27,14 @@ lazy_static! {
 #[derive(Debug, Deserialize)]
 pub struct RfcbotConfig {
     fcp_behaviors: BTreeMap<String, FcpBehavior>,
+    #[serde(default)]
+    fcp_durations: FcpDurations,
+    #[serde(default)]
+    quorum_policies: BTreeMap<String, QuorumPolicy>,
+    #[serde(default)]
+    team_leads: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    resolve_for: ResolveForPolicy,
     teams: RfcbotTeams,
     #[serde(skip)]
     cached_teams: TeamsMap,
@@ -31,9 +42,7 @@ pub struct RfcbotConfig {
 
 impl RfcbotConfig {
     /// Retrive an iterator over all the team labels.
-    pub fn team_labels(&self) -> impl Iterator<Item = &TeamLabel> {
-        self.teams().map(|(k, _)| k)
-    }
+    pub fn team_labels(&self) -> impl Iterator<Item = &TeamLabel> { self.teams().map(|(k, _)| k) }
 
     /// Retrive an iterator over all the (team label, team) pairs.
     pub fn teams(&self) -> impl Iterator<Item = (&TeamLabel, &Team)> {
@@ -59,6 +68,54 @@ impl RfcbotConfig {
             .unwrap_or_default()
     }
 
+    /// Does this repo have finished FCPs wait on review with the `S-waiting-on-review` label?
+    pub fn should_ffcp_add_waiting_on_review(&self, repo: &str) -> bool {
+        self.fcp_behaviors
+            .get(repo)
+            .map(|fcp| fcp.waiting_on_review)
+            .unwrap_or_default()
+    }
+
+    /// Does an FCP in this repo which a concern paused pick up where it left off once the
+    /// concerns are resolved, rather than start over?
+    pub fn should_fcp_resume_after_concern(&self, repo: &str) -> bool {
+        self.fcp_behaviors
+            .get(repo)
+            .map(|fcp| fcp.resume_after_concern)
+            .unwrap_or_default()
+    }
+
+    /// How long does an FCP last on an issue in this repo carrying these labels?
+    ///
+    /// Team durations take precedence over repository durations, which take precedence over the
+    /// global default. If several of the labeled teams have a duration, the longest one wins.
+    pub fn fcp_duration(&self, repo: &str, labels: &[String]) -> Duration {
+        let durations = &self.fcp_durations;
+        let days = labels
+            .iter()
+            .filter_map(|label| durations.teams.get(label))
+            .max()
+            .or_else(|| durations.repos.get(repo))
+            .copied()
+            .unwrap_or(durations.default_days);
+        Duration::days(days)
+    }
+
+    /// Which quorum policy does this team use to decide when an FCP may start?
+    pub fn quorum_policy(&self, label: &str) -> QuorumPolicy {
+        self.quorum_policies.get(label).copied().unwrap_or_default()
+    }
+
+    /// Is this user one of the leads of this team?
+    pub fn is_team_lead(&self, label: &str, login: &str) -> bool {
+        self.team_leads
+            .get(label)
+            .map_or(false, |leads| leads.iter().any(|lead| lead == login))
+    }
+
+    /// Who besides team leads may resolve concerns raised by someone else?
+    pub fn resolve_for_policy(&self) -> ResolveForPolicy { self.resolve_for }
+
     // Update the list of teams from external sources, if needed
     fn update(&mut self) -> Result<(), DashError> {
         #[derive(Deserialize)]
@@ -79,6 +136,103 @@ pub struct FcpBehavior {
     close: bool,
     #[serde(default)]
     postpone: bool,
+    #[serde(default)]
+    resume_after_concern: bool,
+    #[serde(default)]
+    waiting_on_review: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FcpDurations {
+    #[serde(default = "default_fcp_duration_days")]
+    default_days: i64,
+    #[serde(default)]
+    repos: BTreeMap<String, i64>,
+    #[serde(default)]
+    teams: BTreeMap<String, i64>,
+}
+
+impl Default for FcpDurations {
+    fn default() -> Self {
+        FcpDurations {
+            default_days: DEFAULT_FCP_DURATION_DAYS,
+            repos: BTreeMap::new(),
+            teams: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_fcp_duration_days() -> i64 { DEFAULT_FCP_DURATION_DAYS }
+
+/// The rule deciding whether enough reviewers have approved a proposal for its FCP to start.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuorumPolicy {
+    /// A majority of reviewers approve, and at most 2 approvals are outstanding.
+    #[default]
+    Standard,
+    /// A simple majority of reviewers approve.
+    Majority,
+    /// At least this fraction of the reviewers approve.
+    Fraction(f64),
+    /// Every reviewer approves.
+    Unanimous,
+    /// At most this many approvals are outstanding.
+    MaxOutstanding(usize),
+}
+
+impl QuorumPolicy {
+    /// Is the policy satisfied given the number of complete and outstanding reviews? No policy
+    /// is satisfied without a single approval, e.g. when every reviewer abstained.
+    pub fn is_met(self, complete: usize, outstanding: usize) -> bool {
+        if complete == 0 {
+            return false;
+        }
+
+        match self {
+            QuorumPolicy::Standard => outstanding < complete && outstanding < 3,
+            QuorumPolicy::Majority => outstanding < complete,
+            QuorumPolicy::Fraction(fraction) => {
+                complete as f64 >= fraction * (complete + outstanding) as f64
+            }
+            QuorumPolicy::Unanimous => outstanding == 0,
+            QuorumPolicy::MaxOutstanding(max) => outstanding <= max,
+        }
+    }
+
+    /// Describe the condition this policy imposes, e.g. "all reviewers approve".
+    pub fn describe(self) -> String {
+        match self {
+            QuorumPolicy::Standard => {
+                "a majority of reviewers approve (and at most 2 approvals are outstanding)"
+                    .to_string()
+            }
+            QuorumPolicy::Majority => "a majority of reviewers approve".to_string(),
+            QuorumPolicy::Fraction(fraction) => format!(
+                "at least {}% of reviewers approve",
+                (fraction * 100.0).round()
+            ),
+            QuorumPolicy::Unanimous => "all reviewers approve".to_string(),
+            QuorumPolicy::MaxOutstanding(1) => "at most 1 approval is outstanding".to_string(),
+            QuorumPolicy::MaxOutstanding(max) => {
+                format!("at most {} approvals are outstanding", max)
+            }
+        }
+    }
+}
+
+/// Who besides the leads of the teams labeled on an issue may resolve a concern on it on behalf
+/// of whoever raised it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolveForPolicy {
+    /// Nobody else.
+    #[default]
+    Leads,
+    /// Members of those teams, for concerns raised by people who are no longer on any team.
+    DepartedMembers,
+    /// Members of those teams, for any concern.
+    AnyMember,
 }
 
 // This enum definition mixes both struct-style and tuple-style variants: this is intentionally
@@ -99,9 +253,7 @@ pub struct Team {
 }
 
 impl Team {
-    pub fn ping(&self) -> &str {
-        &self.ping
-    }
+    pub fn ping(&self) -> &str { &self.ping }
 
     pub fn member_logins(&self) -> impl Iterator<Item = &str> {
         self.members.iter().map(std::string::String::as_str)
@@ -200,15 +352,36 @@ pub mod test {
 [fcp_behaviors."rust-lang/alpha"]
 close = true
 postpone = true
+resume_after_concern = true
 
 [fcp_behaviors."foobar/beta"]
 close = false
+waiting_on_review = true
 
 [fcp_behaviors."bazquux/gamma"]
 postpone = false
 
 [fcp_behaviors."wibble/epsilon"]
 
+[fcp_durations]
+default_days = 10
+
+[fcp_durations.repos]
+"rust-lang/alpha" = 14
+"foobar/beta" = 5
+
+[fcp_durations.teams]
+T-avengers = 7
+justice-league = 3
+
+[quorum_policies]
+T-avengers = "unanimous"
+justice-league = { fraction = 0.75 }
+T-guardians = { max-outstanding = 1 }
+
+[team_leads]
+T-avengers = ["captainamerica"]
+
 [teams]
 
 [teams.T-avengers]
@@ -287,6 +460,129 @@ members = [
         assert!(!cfg.should_ffcp_auto_postpone("wibble/epsilon"));
         assert!(!cfg.should_ffcp_auto_close("random"));
         assert!(!cfg.should_ffcp_auto_postpone("random"));
+
+        // Paused FCPs restart unless configured to resume:
+        assert!(cfg.should_fcp_resume_after_concern("rust-lang/alpha"));
+        assert!(!cfg.should_fcp_resume_after_concern("foobar/beta"));
+        assert!(!cfg.should_fcp_resume_after_concern("random"));
+
+        // Only repos which use it get S-waiting-on-review:
+        assert!(cfg.should_ffcp_add_waiting_on_review("foobar/beta"));
+        assert!(!cfg.should_ffcp_add_waiting_on_review("rust-lang/alpha"));
+        assert!(!cfg.should_ffcp_add_waiting_on_review("random"));
+    }
+
+    #[test]
+    fn fcp_duration_correct() {
+        let cfg = &*TEST_SETUP;
+        let labels = |ls: &[&str]| ls.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+
+        // Falls back to the global default:
+        assert_eq!(cfg.fcp_duration("random", &[]), Duration::days(10));
+        assert_eq!(
+            cfg.fcp_duration("random", &labels(&["T-random"])),
+            Duration::days(10)
+        );
+
+        // Repositories override the default:
+        assert_eq!(cfg.fcp_duration("rust-lang/alpha", &[]), Duration::days(14));
+        assert_eq!(cfg.fcp_duration("foobar/beta", &[]), Duration::days(5));
+
+        // Teams override repositories, and the longest team duration wins:
+        assert_eq!(
+            cfg.fcp_duration("rust-lang/alpha", &labels(&["justice-league"])),
+            Duration::days(3)
+        );
+        assert_eq!(
+            cfg.fcp_duration("random", &labels(&["justice-league", "T-avengers"])),
+            Duration::days(7)
+        );
+
+        // The whole section is optional:
+        let cfg = read_rfcbot_cfg_from("[fcp_behaviors]\n[teams]\n");
+        assert_eq!(cfg.fcp_duration("random", &[]), Duration::days(10));
+    }
+
+    #[test]
+    fn team_leads_correct() {
+        let cfg = &*TEST_SETUP;
+
+        assert!(cfg.is_team_lead("T-avengers", "captainamerica"));
+        assert!(!cfg.is_team_lead("T-avengers", "hulk"));
+        assert!(!cfg.is_team_lead("justice-league", "captainamerica"));
+        assert_eq!(cfg.resolve_for_policy(), ResolveForPolicy::Leads);
+
+        let cfg =
+            read_rfcbot_cfg_from("resolve_for = \"departed-members\"\n[fcp_behaviors]\n[teams]\n");
+        assert_eq!(cfg.resolve_for_policy(), ResolveForPolicy::DepartedMembers);
+    }
+
+    #[test]
+    fn quorum_policy_parser_correct() {
+        let cfg = &*TEST_SETUP;
+
+        assert_eq!(cfg.quorum_policy("T-avengers"), QuorumPolicy::Unanimous);
+        assert_eq!(
+            cfg.quorum_policy("justice-league"),
+            QuorumPolicy::Fraction(0.75)
+        );
+        assert_eq!(
+            cfg.quorum_policy("T-guardians"),
+            QuorumPolicy::MaxOutstanding(1)
+        );
+        assert_eq!(cfg.quorum_policy("random"), QuorumPolicy::Standard);
+    }
+
+    #[test]
+    fn quorum_policy_is_met() {
+        use self::QuorumPolicy::*;
+
+        // (policy, complete, outstanding, expected)
+        let cases = [
+            (Standard, 5, 2, true),
+            (Standard, 5, 3, false),
+            (Standard, 2, 2, false),
+            (Standard, 0, 0, false),
+            (Majority, 5, 4, true),
+            (Majority, 4, 4, false),
+            (Fraction(0.75), 3, 1, true),
+            (Fraction(0.75), 2, 1, false),
+            (Unanimous, 4, 0, true),
+            (Unanimous, 4, 1, false),
+            (MaxOutstanding(1), 1, 1, true),
+            (MaxOutstanding(1), 8, 2, false),
+        ];
+
+        for &(policy, complete, outstanding, expected) in &cases {
+            assert_eq!(
+                policy.is_met(complete, outstanding),
+                expected,
+                "{:?} with {} complete and {} outstanding",
+                policy,
+                complete,
+                outstanding
+            );
+        }
+    }
+
+    #[test]
+    fn quorum_policy_needs_an_approval() {
+        use self::QuorumPolicy::*;
+
+        // every reviewer abstained, so nobody is complete or outstanding
+        for &policy in &[
+            Standard,
+            Majority,
+            Fraction(0.75),
+            Unanimous,
+            MaxOutstanding(1),
+        ] {
+            assert!(!policy.is_met(0, 0), "{:?} met by all abstentions", policy);
+        }
+
+        // a single approval is enough once everybody else abstained
+        assert!(Unanimous.is_met(1, 0));
+        assert!(MaxOutstanding(0).is_met(1, 0));
     }
 
     #[test]
@@ -1,4 +1,5 @@
 use crate::error::DashResult;
+use chrono::{NaiveDateTime, Utc};
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
@@ -20,6 +21,41 @@ where
     })
 }
 
+/// Like `spawn_thread`, but `f` reports the next deadline it knows about (if any), and the thread
+/// wakes up in time for that deadline instead of always waiting for the full interval.
+pub(crate) fn spawn_scheduled_thread<F>(
+    name: &'static str,
+    interval_minutes: u64,
+    f: F,
+) -> JoinHandle<()>
+where
+    F: Fn() -> DashResult<Option<NaiveDateTime>> + Send + 'static,
+{
+    let interval = Duration::from_secs(interval_minutes * 60);
+    // a little slack so that we don't wake up a hair before the deadline is actually reached
+    let slack = Duration::from_secs(1);
+    thread::spawn(move || loop {
+        let mut duration = interval;
+        match f() {
+            Ok(Some(deadline)) => {
+                info!("the {} thread's next deadline is at {} UTC", name, deadline);
+                // a deadline in the past means it couldn't be handled, retry on the usual schedule
+                if let Ok(until) = (deadline - Utc::now().naive_utc()).to_std() {
+                    duration = duration.min(until + slack);
+                }
+            }
+            Ok(None) => debug!("the {} thread has no upcoming deadlines", name),
+            Err(err) => error!("the {} thread failed an iteration: {:?}", name, err),
+        }
+        info!(
+            "{} thread sleeping for {} seconds",
+            name,
+            duration.as_secs()
+        );
+        thread::sleep(duration);
+    })
+}
+
 #[cfg(test)]
 pub(crate) fn setup_test_env() {
     use std::path::Path;